@@ -0,0 +1,184 @@
+//! The `--cache` result cache. Files whose contents, linter, and rule options
+//! are unchanged since the last run reuse their stored diagnostics instead of
+//! going through the Node bridge again.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use julietscript_lint::{
+    run_node_linter_chunked, LintDiagnostic, LintFileResult, LintFix, LintInputFile, LinterOptions,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::fnv1a_64;
+
+const CACHE_FILE_NAME: &str = ".julietlint-cache.json";
+
+#[derive(Deserialize, Serialize)]
+struct CacheFile {
+    /// Fingerprint of the linter and rule options the entries were made with.
+    key: String,
+    files: BTreeMap<String, CacheEntry>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct CacheEntry {
+    hash: String,
+    diagnostics: Vec<CachedDiagnostic>,
+}
+
+/// Reports leave out a diagnostic's fix; the cache keeps it so `--fix` works
+/// on files that were not re-linted.
+#[derive(Deserialize, Serialize)]
+struct CachedDiagnostic {
+    #[serde(flatten)]
+    diagnostic: LintDiagnostic,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fix: Option<LintFix>,
+}
+
+pub struct ResultCache {
+    path: PathBuf,
+    key: String,
+    files: BTreeMap<String, CacheEntry>,
+}
+
+impl ResultCache {
+    /// Reads `DIR/.julietlint-cache.json`. A missing file starts an empty
+    /// cache; an unreadable one, or one made by a different linter or rule
+    /// set, is ignored.
+    pub fn load(dir: &Path, key: String) -> Self {
+        let path = dir.join(CACHE_FILE_NAME);
+        let files = match fs::read_to_string(&path) {
+            Ok(text) => match serde_json::from_str::<CacheFile>(&text) {
+                Ok(cache) if cache.key == key => cache.files,
+                Ok(_) => BTreeMap::new(),
+                Err(error) => {
+                    warn(&path, "ignoring unreadable cache", &error);
+                    BTreeMap::new()
+                }
+            },
+            Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => {
+                warn(&path, "ignoring unreadable cache", &error);
+                BTreeMap::new()
+            }
+        };
+        Self { path, key, files }
+    }
+
+    /// Lints the files without a current cache entry and returns their
+    /// results along with the cached ones. Each bridge's time is appended to
+    /// `worker_times`.
+    pub fn lint(
+        &self,
+        linter: &LinterOptions,
+        inputs: &[LintInputFile],
+        jobs: usize,
+        max_inflight: usize,
+        worker_times: &mut Vec<Duration>,
+    ) -> Result<Vec<LintFileResult>> {
+        let mut results = Vec::with_capacity(inputs.len());
+        let mut misses = Vec::new();
+        for input in inputs {
+            match self.lookup(input) {
+                Some(result) => results.push(result),
+                None => misses.push(input.clone()),
+            }
+        }
+        linter
+            .log
+            .info(|| format!("cache: {} hit(s), {} miss(es)", results.len(), misses.len()));
+        if !misses.is_empty() {
+            let (linted, durations) = run_node_linter_chunked(linter, &misses, jobs, max_inflight)?;
+            results.extend(linted);
+            worker_times.extend(durations);
+        }
+        Ok(results)
+    }
+
+    fn lookup(&self, input: &LintInputFile) -> Option<LintFileResult> {
+        let entry = self.files.get(&cache_path(input))?;
+        if entry.hash != content_hash(input) {
+            return None;
+        }
+        Some(LintFileResult {
+            path: input.path.clone(),
+            diagnostics: entry
+                .diagnostics
+                .iter()
+                .map(|cached| LintDiagnostic {
+                    fix: cached.fix.clone(),
+                    ..cached.diagnostic.clone()
+                })
+                .collect(),
+            sources: Vec::new(),
+        })
+    }
+
+    /// Records `results` and writes the cache, creating its directory if
+    /// needed. Entries for files that no longer exist are dropped; ones this
+    /// run did not cover, such as outside an `--input-list` subset, are kept.
+    /// Failures only warn: the run already has its results.
+    pub fn save(mut self, results: &[LintFileResult], inputs: &[LintInputFile]) {
+        self.files.retain(|path, _| Path::new(path).is_file());
+        for result in results {
+            let Some(input) = inputs.iter().find(|input| input.path == result.path) else {
+                continue;
+            };
+            let entry = CacheEntry {
+                hash: content_hash(input),
+                diagnostics: result
+                    .diagnostics
+                    .iter()
+                    .map(|diagnostic| CachedDiagnostic {
+                        diagnostic: diagnostic.clone(),
+                        fix: diagnostic.fix.clone(),
+                    })
+                    .collect(),
+            };
+            self.files.insert(cache_path(input), entry);
+        }
+
+        let cache = CacheFile {
+            key: self.key,
+            files: self.files,
+        };
+        let written = serde_json::to_string(&cache)
+            .map_err(io::Error::from)
+            .and_then(|text| {
+                if let Some(dir) = self.path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                fs::write(&self.path, text)
+            });
+        if let Err(error) = written {
+            warn(&self.path, "could not write cache", &error);
+        }
+    }
+}
+
+/// Entries are keyed by the file on disk, so `--relative-to` does not split
+/// the cache.
+fn cache_path(input: &LintInputFile) -> String {
+    input.file.display().to_string()
+}
+
+fn content_hash(input: &LintInputFile) -> String {
+    let bom = if input.bom { "\u{feff}" } else { "" };
+    format!(
+        "{:016x}",
+        fnv1a_64(format!("{bom}{}", input.source).as_bytes())
+    )
+}
+
+fn warn(path: &Path, what: &str, error: &dyn std::fmt::Display) {
+    eprintln!(
+        "julietscript-lint: warning: {what} '{}': {error}; continuing without it.",
+        path.display()
+    );
+}
@@ -0,0 +1,1062 @@
+//! The linting engine behind the `julietscript-lint` binary: file collection,
+//! the Node.js bridge that runs `linter.js`, and the result types it returns.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+pub const EMBEDDED_LINTER_SOURCE: &str = include_str!("linter.js");
+
+const NODE_BRIDGE_SCRIPT: &str = r#"
+const fs = require("fs");
+
+const linterPath = process.env.JULIETSCRIPT_LINTER_PATH;
+const linterSource = process.env.JULIETSCRIPT_LINTER_SOURCE;
+
+const mode = process.env.JULIETSCRIPT_BRIDGE_MODE || "lint";
+
+// The linter is loaded or compiled exactly once per bridge process; every file
+// in the payload reuses the same functions.
+let lintJulietScript;
+let analyzeJulietScript;
+let parseJulietScript;
+if (linterPath) {
+  try {
+    ({ lintJulietScript, analyzeJulietScript, parseJulietScript } = require(linterPath));
+  } catch (error) {
+    console.error(`Failed to load JulietScript linter from ${linterPath}: ${error.message}`);
+    process.exit(1);
+  }
+} else if (linterSource) {
+  try {
+    const module = { exports: {} };
+    const compile = new Function("module", "exports", "require", linterSource);
+    compile(module, module.exports, require);
+    ({ lintJulietScript, analyzeJulietScript, parseJulietScript } = module.exports);
+  } catch (error) {
+    console.error(`Failed to compile embedded JulietScript linter: ${error.message}`);
+    process.exit(1);
+  }
+} else {
+  console.error("No JulietScript linter source available. Set JULIETSCRIPT_LINTER_PATH or JULIETSCRIPT_LINTER_SOURCE.");
+  process.exit(1);
+}
+
+if (typeof lintJulietScript !== "function") {
+  console.error("Loaded JulietScript linter does not export lintJulietScript(source).");
+  process.exit(1);
+}
+
+if (mode === "graph" && typeof analyzeJulietScript !== "function") {
+  console.error("Loaded JulietScript linter does not export analyzeJulietScript(source).");
+  process.exit(1);
+}
+
+if (mode === "ast" && typeof parseJulietScript !== "function") {
+  console.error("Loaded JulietScript linter does not export parseJulietScript(source).");
+  process.exit(1);
+}
+
+let files;
+try {
+  files = JSON.parse(fs.readFileSync(0, "utf8"));
+} catch (error) {
+  console.error(`Failed to parse lint payload: ${error.message}`);
+  process.exit(1);
+}
+
+if (!Array.isArray(files)) {
+  console.error("Lint payload must be an array.");
+  process.exit(1);
+}
+
+let options = {};
+try {
+  options = JSON.parse(process.env.JULIETSCRIPT_LINT_OPTIONS || "{}");
+} catch (error) {
+  console.error(`Failed to parse lint options: ${error.message}`);
+  process.exit(1);
+}
+
+const analyzeFile = (file) => {
+  const fileOptions = file.byteOrderMark ? { ...options, byteOrderMark: true } : options;
+  if (mode === "graph") {
+    return { path: file.path, ...analyzeJulietScript(file.source, fileOptions) };
+  }
+  if (mode === "ast") {
+    return { path: file.path, ast: parseJulietScript(file.source, fileOptions) };
+  }
+  if (options.checkSources && typeof analyzeJulietScript === "function") {
+    const { diagnostics, sources } = analyzeJulietScript(file.source, fileOptions);
+    return { path: file.path, diagnostics, sources: sources || [] };
+  }
+  return {
+    path: file.path,
+    diagnostics: lintJulietScript(file.source, fileOptions),
+  };
+};
+
+// A linter that throws on one file reports the crash as that file's only
+// diagnostic instead of taking the rest of the run down with it.
+const lintFile = (file) => {
+  try {
+    return analyzeFile(file);
+  } catch (error) {
+    const message = error instanceof Error ? error.message : String(error);
+    const start = { line: 0, character: 0 };
+    return {
+      path: file.path,
+      diagnostics: [
+        {
+          severity: "error",
+          message: `Linter crashed: ${message}`,
+          rule: "linter-crash",
+          range: { start, end: start },
+        },
+      ],
+      ...(mode === "graph" ? { artifacts: [] } : {}),
+    };
+  }
+};
+
+if (process.env.JULIETSCRIPT_BRIDGE_STREAM === "1") {
+  // One JSON result per line, written as soon as each file is linted.
+  for (const file of files) {
+    process.stdout.write(`${JSON.stringify(lintFile(file))}\n`);
+  }
+} else {
+  process.stdout.write(JSON.stringify(files.map(lintFile)));
+}
+"#;
+
+pub const UTF8_BOM: char = '\u{feff}';
+
+/// How many times a failed `node` spawn is retried when the failure looks
+/// transient (EAGAIN, ENOMEM).
+pub const DEFAULT_SPAWN_RETRIES: u32 = 2;
+const SPAWN_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Minimal stderr logger for `-v`/`-vv`. Each message is written with a single
+/// `eprintln!`, which holds the stderr lock, so lines never interleave.
+#[derive(Clone, Copy, Default)]
+pub struct Logger {
+    pub verbosity: u8,
+}
+
+impl Logger {
+    pub fn info(&self, message: impl FnOnce() -> String) {
+        if self.verbosity >= 1 {
+            eprintln!("julietscript-lint: {}", message());
+        }
+    }
+
+    pub fn debug(&self, message: impl FnOnce() -> String) {
+        if self.verbosity >= 2 {
+            eprintln!("julietscript-lint: {}", message());
+        }
+    }
+}
+
+/// Where the Node bridge loads the linter from, and which rules it runs.
+#[derive(Clone)]
+pub struct LinterOptions {
+    pub path: Option<PathBuf>,
+    pub allow_embedded: bool,
+    pub rules: RuleSelection,
+    pub log: Logger,
+    /// Retries for transient spawn failures, with exponential backoff.
+    pub spawn_retries: u32,
+    /// Extra `node` flags, placed before the bridge's `-e`. See `parse_node_args`.
+    pub node_args: Vec<String>,
+}
+
+/// Node flags that only tune the runtime. Anything else is rejected, since
+/// many node flags run other code or stop the bridge from running at all.
+const ALLOWED_NODE_FLAGS: &[&str] = &[
+    "--no-warnings",
+    "--no-deprecation",
+    "--trace-warnings",
+    "--trace-deprecation",
+    "--trace-uncaught",
+    "--enable-source-maps",
+];
+
+/// Allowed node flags that take a numeric value, written `--flag=N`.
+const ALLOWED_NODE_VALUE_FLAGS: &[&str] = &[
+    "--max-old-space-size",
+    "--max-semi-space-size",
+    "--stack-size",
+    "--stack-trace-limit",
+];
+
+/// Splits `--bridge-node-args` on whitespace. Only the runtime-tuning flags
+/// in `ALLOWED_NODE_FLAGS` and `ALLOWED_NODE_VALUE_FLAGS` are accepted, so
+/// the bridge stays the script node runs. Flags that take a value must use
+/// `--flag=value`.
+pub fn parse_node_args(text: &str) -> Result<Vec<String>> {
+    let args: Vec<String> = text.split_whitespace().map(str::to_owned).collect();
+    for arg in &args {
+        if !arg.starts_with('-') || arg == "-" || arg == "--" {
+            bail!("--bridge-node-args only accepts node flags, found '{arg}'. Write flag values as --flag=value");
+        }
+        let allowed = match arg.split_once('=') {
+            Some((name, value)) => {
+                ALLOWED_NODE_VALUE_FLAGS.contains(&name)
+                    && !value.is_empty()
+                    && value.bytes().all(|byte| byte.is_ascii_digit())
+            }
+            None => ALLOWED_NODE_FLAGS.contains(&arg.as_str()),
+        };
+        if !allowed {
+            let name = arg.split('=').next().unwrap_or(arg);
+            bail!(
+                "--bridge-node-args cannot include '{name}': only {} and {}=N are allowed",
+                ALLOWED_NODE_FLAGS.join(", "),
+                ALLOWED_NODE_VALUE_FLAGS.join("=N, ")
+            );
+        }
+    }
+    Ok(args)
+}
+
+/// Passed to `lintJulietScript(source, options)` as its second argument.
+#[derive(Clone, Serialize, Debug)]
+pub struct RuleSelection {
+    pub enable: Vec<String>,
+    pub disable: Vec<String>,
+    /// Limit for the opt-in `max-line-length` rule; the linter defaults to 120.
+    #[serde(rename = "maxLineLength", skip_serializing_if = "Option::is_none")]
+    pub max_line_length: Option<usize>,
+    /// Upper bound for the opt-in `rubric-points` rule.
+    #[serde(rename = "maxRubricPoints", skip_serializing_if = "Option::is_none")]
+    pub max_rubric_points: Option<u64>,
+    /// Exact total for the opt-in `rubric-points` rule; overrides the bound.
+    #[serde(rename = "exactRubricPoints", skip_serializing_if = "Option::is_none")]
+    pub exact_rubric_points: Option<u64>,
+    /// Candidate limit for the opt-in `search-budget` rule; the linter
+    /// defaults to 1000.
+    #[serde(rename = "maxSearchBudget", skip_serializing_if = "Option::is_none")]
+    pub max_search_budget: Option<u64>,
+    /// Rule preset: `minimal`, `recommended` (the linter's default), or
+    /// `strict`. `enable` and `disable` apply on top.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Artifacts may be created outside the linted text, so unknown artifact
+    /// references are not reported. Set for `--stdin` buffers.
+    #[serde(
+        rename = "externalArtifacts",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub external_artifacts: bool,
+    /// Ask the bridge for each file's `julietArtifactSourceFiles` paths so
+    /// the caller can check they exist.
+    #[serde(rename = "checkSources", skip_serializing_if = "std::ops::Not::not")]
+    pub check_sources: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LintInputFile {
+    pub path: String,
+    pub source: String,
+    /// Native path on disk; `path` is the normalized form shown in output.
+    #[serde(skip)]
+    pub file: PathBuf,
+    /// The file on disk starts with a UTF-8 byte order mark, which is stripped
+    /// from `source`. Sent to the linter as `byteOrderMark`.
+    #[serde(
+        rename = "byteOrderMark",
+        default,
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub bom: bool,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct LintPosition {
+    pub line: usize,
+    pub character: usize,
+    /// Byte offset into the file, filled in on the Rust side for JSON output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct LintRange {
+    pub start: LintPosition,
+    // Older linters only report a start position.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<LintPosition>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct LintDiagnostic {
+    pub severity: String,
+    pub message: String,
+    /// Rule ID such as `missing-semicolon`. Older linters do not report one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    pub range: LintRange,
+    #[serde(skip_serializing)]
+    pub fix: Option<LintFix>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct LintFix {
+    pub range: LintFixRange,
+    pub text: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct LintFixRange {
+    pub start: LintPosition,
+    pub end: LintPosition,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct LintFileResult {
+    pub path: String,
+    pub diagnostics: Vec<LintDiagnostic>,
+    /// Source file paths the script seeds artifacts from, as written. Only
+    /// reported when [`RuleSelection::check_sources`] is set.
+    #[serde(default, skip_serializing)]
+    pub sources: Vec<SourceReference>,
+}
+
+/// A path in a `julietArtifactSourceFiles [...]` list and where it appears.
+#[derive(Clone, Deserialize)]
+pub struct SourceReference {
+    pub path: String,
+    pub range: LintRange,
+}
+
+#[derive(Default, Serialize)]
+pub struct LintSummary {
+    pub files: usize,
+    pub issues: usize,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+impl LintSummary {
+    pub fn from_results(results: &[LintFileResult]) -> Self {
+        let mut summary = LintSummary {
+            files: results.len(),
+            ..LintSummary::default()
+        };
+        for diagnostic in results.iter().flat_map(|file| &file.diagnostics) {
+            summary.issues += 1;
+            match diagnostic.severity.as_str() {
+                "error" => summary.errors += 1,
+                "warning" => summary.warnings += 1,
+                _ => {}
+            }
+        }
+        summary
+    }
+}
+
+/// Which linter implementation [`lint_directory`] runs under Node.js.
+#[derive(Clone, Debug, Default)]
+pub enum Runtime {
+    /// The `linter.js` compiled into this crate.
+    #[default]
+    Embedded,
+    /// A `linter.js` file on disk, as with the CLI's `--linter`.
+    LinterFile(PathBuf),
+}
+
+/// Options for [`lint_directory`].
+#[derive(Clone, Debug, Default)]
+pub struct LintOptions {
+    /// Glob patterns, relative to the root, for files to skip.
+    pub excludes: Vec<String>,
+    /// Rule IDs whose diagnostics are dropped.
+    pub disabled_rules: Vec<String>,
+    pub runtime: Runtime,
+    /// Retries for transient spawn failures, as with the CLI's
+    /// `--spawn-retries`. `None` uses [`DEFAULT_SPAWN_RETRIES`].
+    pub spawn_retries: Option<u32>,
+}
+
+/// Per-file diagnostics, sorted by path, with aggregate counts.
+pub struct LintReport {
+    pub files: Vec<LintFileResult>,
+    pub summary: LintSummary,
+}
+
+/// Lints every file under `root` matching `globs` (or `**/*.julietscript`
+/// when `globs` is empty). Matching no files is not an error; the report is
+/// simply empty.
+pub fn lint_directory(root: &Path, globs: &[&str], options: &LintOptions) -> Result<LintReport> {
+    let root = fs::canonicalize(root)
+        .with_context(|| format!("failed to resolve root directory '{}'", root.display()))?;
+    let log = Logger::default();
+    let patterns: Vec<String> = if globs.is_empty() {
+        vec![extension_glob(&["julietscript".to_owned()])]
+    } else {
+        globs.iter().map(|pattern| (*pattern).to_owned()).collect()
+    };
+
+    let paths = collect_files(&root, &patterns, log, true)?;
+    let paths = filter_excluded(&root, paths, &options.excludes)?;
+    if paths.is_empty() {
+        return Ok(LintReport {
+            files: Vec::new(),
+            summary: LintSummary::default(),
+        });
+    }
+
+    let inputs = load_files(&paths)?;
+    let linter = LinterOptions {
+        path: match &options.runtime {
+            Runtime::Embedded => None,
+            Runtime::LinterFile(path) => Some(path.clone()),
+        },
+        allow_embedded: true,
+        rules: RuleSelection {
+            enable: Vec::new(),
+            disable: options.disabled_rules.clone(),
+            max_line_length: None,
+            max_rubric_points: None,
+            exact_rubric_points: None,
+            max_search_budget: None,
+            external_artifacts: false,
+            check_sources: false,
+            profile: None,
+        },
+        log,
+        spawn_retries: options.spawn_retries.unwrap_or(DEFAULT_SPAWN_RETRIES),
+        node_args: Vec::new(),
+    };
+    let mut files = run_node_linter(&linter, &inputs)?;
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    let summary = LintSummary::from_results(&files);
+    Ok(LintReport { files, summary })
+}
+
+/// Drops every path whose location relative to `root` matches one of the
+/// `excludes` glob patterns (brace groups allowed).
+pub fn filter_excluded(
+    root: &Path,
+    mut paths: Vec<PathBuf>,
+    excludes: &[String],
+) -> Result<Vec<PathBuf>> {
+    let excludes = excludes
+        .iter()
+        .flat_map(|pattern| expand_braces(pattern))
+        .map(|pattern| {
+            glob::Pattern::new(&pattern)
+                .with_context(|| format!("invalid exclude pattern '{pattern}'"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    paths.retain(|path| {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        !excludes
+            .iter()
+            .any(|exclude| exclude.matches_path(relative))
+    });
+    Ok(paths)
+}
+
+pub fn ensure_embedded_allowed(allow_embedded: bool) -> Result<()> {
+    if !allow_embedded {
+        bail!(
+            "--no-default-linter requires a linter from --linter FILE or JULIETSCRIPT_LINTER_PATH"
+        );
+    }
+    Ok(())
+}
+
+/// Expands `patterns` under `root` into a sorted, de-duplicated file list.
+/// With `canonicalize`, symlinks are resolved so a file reached two ways is
+/// linted once; without it, files keep the path they were matched by.
+pub fn collect_files(
+    root: &Path,
+    patterns: &[String],
+    log: Logger,
+    canonicalize: bool,
+) -> Result<Vec<PathBuf>> {
+    let mut files = BTreeSet::new();
+
+    for pattern in patterns {
+        for expanded in expand_braces(pattern) {
+            let resolved_pattern = if Path::new(&expanded).is_absolute() {
+                expanded
+            } else {
+                root.join(&expanded).to_string_lossy().into_owned()
+            };
+            log.info(|| format!("glob '{pattern}' resolved to '{resolved_pattern}'"));
+
+            let entries = glob::glob(&resolved_pattern)
+                .with_context(|| format!("invalid glob pattern '{}'", pattern))?;
+
+            for entry in entries {
+                let path = entry
+                    .with_context(|| format!("error while expanding glob pattern '{}'", pattern))?;
+                if path.is_file() {
+                    let path = if canonicalize {
+                        fs::canonicalize(path).context("failed to canonicalize matched path")?
+                    } else {
+                        path
+                    };
+                    log.info(|| format!("matched '{}'", path.display()));
+                    files.insert(path);
+                }
+            }
+        }
+    }
+
+    Ok(files.into_iter().collect())
+}
+
+/// Builds the `**/*.{ext,...}` pattern that recognizes JulietScript files.
+pub fn extension_glob(extensions: &[String]) -> String {
+    let extensions: Vec<&str> = extensions
+        .iter()
+        .map(|extension| extension.trim_start_matches('.'))
+        .collect();
+    match extensions.as_slice() {
+        [single] => format!("**/*.{single}"),
+        _ => format!("**/*.{{{}}}", extensions.join(",")),
+    }
+}
+
+/// Expands shell-style `{a,b}` groups, which `glob` does not understand, into
+/// one pattern per alternative. Groups without a top-level comma (including
+/// `{}`) and unbalanced braces are kept literally.
+pub fn expand_braces(pattern: &str) -> Vec<String> {
+    let mut search_from = 0usize;
+    while let Some(relative_open) = pattern[search_from..].find('{') {
+        let open = search_from + relative_open;
+        let mut depth = 0usize;
+        let mut close = None;
+        let mut commas = Vec::new();
+        for (index, ch) in pattern[open..].char_indices() {
+            match ch {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        close = Some(open + index);
+                        break;
+                    }
+                }
+                ',' if depth == 1 => commas.push(open + index),
+                _ => {}
+            }
+        }
+
+        let Some(close) = close else {
+            break;
+        };
+        if commas.is_empty() {
+            search_from = open + 1;
+            continue;
+        }
+
+        let prefix = &pattern[..open];
+        let suffix = &pattern[close + 1..];
+        let mut bounds = vec![open];
+        bounds.extend(commas);
+        bounds.push(close);
+        return bounds
+            .windows(2)
+            .flat_map(|window| {
+                let alternative = &pattern[window[0] + 1..window[1]];
+                expand_braces(&format!("{prefix}{alternative}{suffix}"))
+            })
+            .collect();
+    }
+
+    vec![pattern.to_owned()]
+}
+
+/// A file skipped by [`partition_oversized`].
+pub struct OversizedFile {
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// Splits off files larger than `max_bytes`, returned with their sizes. A
+/// multi-megabyte "script" is usually a misnamed build output, and sending it
+/// through the Node bridge only slows the run down or exhausts memory.
+pub fn partition_oversized(
+    paths: &[PathBuf],
+    max_bytes: u64,
+) -> Result<(Vec<PathBuf>, Vec<OversizedFile>)> {
+    let mut kept = Vec::with_capacity(paths.len());
+    let mut oversized = Vec::new();
+    for path in paths {
+        let size = fs::metadata(path)
+            .with_context(|| format!("failed to read '{}'", path.display()))?
+            .len();
+        if size > max_bytes {
+            oversized.push(OversizedFile {
+                path: path.clone(),
+                size,
+            });
+        } else {
+            kept.push(path.clone());
+        }
+    }
+    Ok((kept, oversized))
+}
+
+pub fn load_files(paths: &[PathBuf]) -> Result<Vec<LintInputFile>> {
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("failed to read '{}'", path.display()))?;
+        let (source, bom) = match source.strip_prefix(UTF8_BOM) {
+            Some(rest) => (rest.to_owned(), true),
+            None => (source, false),
+        };
+        files.push(LintInputFile {
+            path: display_path(path),
+            source,
+            file: path.clone(),
+            bom,
+        });
+    }
+    Ok(files)
+}
+
+/// Renders a path for diagnostics. On Windows, canonical paths carry a `\\?\`
+/// verbatim prefix and backslash separators; both are normalized so output
+/// looks the same on every platform.
+pub fn display_path(path: &Path) -> String {
+    let rendered = path.display().to_string();
+    if cfg!(windows) {
+        normalize_windows_path(&rendered)
+    } else {
+        rendered
+    }
+}
+
+fn normalize_windows_path(path: &str) -> String {
+    let stripped = if let Some(unc) = path.strip_prefix(r"\\?\UNC\") {
+        format!(r"\\{unc}")
+    } else {
+        path.strip_prefix(r"\\?\").unwrap_or(path).to_owned()
+    };
+    stripped.replace('\\', "/")
+}
+
+pub fn run_node_linter(
+    linter: &LinterOptions,
+    files: &[LintInputFile],
+) -> Result<Vec<LintFileResult>> {
+    run_node_bridge(linter, files, "lint")
+}
+
+/// Splits `files` into `jobs` chunks and lints each with its own Node bridge.
+/// At most `max_inflight` bridges run at once; the remaining chunks wait for a
+/// free slot. Results come back in chunk order, along with how long each
+/// chunk's bridge took, for `--timings`.
+pub fn run_node_linter_chunked(
+    linter: &LinterOptions,
+    files: &[LintInputFile],
+    jobs: usize,
+    max_inflight: usize,
+) -> Result<(Vec<LintFileResult>, Vec<Duration>)> {
+    let jobs = jobs.clamp(1, files.len().max(1));
+    if jobs == 1 {
+        let started = Instant::now();
+        let results = run_node_linter(linter, files)?;
+        return Ok((results, vec![started.elapsed()]));
+    }
+    let chunks: Vec<&[LintInputFile]> = files.chunks(files.len().div_ceil(jobs)).collect();
+    let workers = max_inflight.clamp(1, chunks.len());
+    linter.log.info(|| {
+        format!(
+            "linting {} chunk(s) with at most {workers} bridge(s) at once",
+            chunks.len()
+        )
+    });
+
+    let next = AtomicUsize::new(0);
+    type Slot = Option<(Result<Vec<LintFileResult>>, Duration)>;
+    let slots: Mutex<Vec<Slot>> = Mutex::new((0..chunks.len()).map(|_| None).collect());
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(chunk) = chunks.get(index) else {
+                    break;
+                };
+                let started = Instant::now();
+                let result = run_node_linter(linter, chunk);
+                slots.lock().unwrap_or_else(|error| error.into_inner())[index] =
+                    Some((result, started.elapsed()));
+            });
+        }
+    });
+
+    let mut results = Vec::with_capacity(files.len());
+    let mut durations = Vec::with_capacity(chunks.len());
+    let outputs = slots
+        .into_inner()
+        .unwrap_or_else(|error| error.into_inner());
+    for output in outputs {
+        let (result, duration) = output.context("a lint worker exited without a result")?;
+        results.extend(result?);
+        durations.push(duration);
+    }
+    Ok((results, durations))
+}
+
+/// Runs the Node bridge in `mode` ("lint", "graph", or "ast") and decodes one result
+/// per input file.
+pub fn run_node_bridge<T: DeserializeOwned>(
+    linter: &LinterOptions,
+    files: &[LintInputFile],
+    mode: &str,
+) -> Result<Vec<T>> {
+    let mut command = node_bridge_command(linter, mode)?;
+    let started = Instant::now();
+    let mut child = spawn_bridge(&mut command, linter)?;
+    send_payload(&mut child, linter, files)?;
+
+    let output = child
+        .wait_with_output()
+        .context("failed while waiting for node bridge process")?;
+    linter
+        .log
+        .info(|| format!("bridge finished in {:.2?}", started.elapsed()));
+    check_bridge_status(output.status, &output.stderr)?;
+
+    serde_json::from_slice(&output.stdout).context("failed to decode JSON results from node bridge")
+}
+
+/// Like `run_node_linter`, but hands each file's result to `on_result` as soon
+/// as the bridge finishes it, in completion order. An error from `on_result`
+/// stops reading and is returned. `ControlFlow::Break` stops the bridge and
+/// returns the results seen so far, including the one that broke.
+pub fn stream_node_linter(
+    linter: &LinterOptions,
+    files: &[LintInputFile],
+    mut on_result: impl FnMut(&mut LintFileResult) -> Result<ControlFlow<()>>,
+) -> Result<Vec<LintFileResult>> {
+    let mut command = node_bridge_command(linter, "lint")?;
+    command.env("JULIETSCRIPT_BRIDGE_STREAM", "1");
+    let started = Instant::now();
+    let mut child = spawn_bridge(&mut command, linter)?;
+    send_payload(&mut child, linter, files)?;
+
+    // Drain stderr on its own thread so a chatty linter cannot block stdout.
+    let mut stderr = child
+        .stderr
+        .take()
+        .context("failed to open stderr for node bridge process")?;
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = stderr.read_to_end(&mut buffer);
+        buffer
+    });
+
+    let stdout = child
+        .stdout
+        .take()
+        .context("failed to open stdout for node bridge process")?;
+    let mut results = Vec::with_capacity(files.len());
+    for line in BufReader::new(stdout).lines() {
+        let line = line.context("failed to read results from node bridge")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut result: LintFileResult =
+            serde_json::from_str(&line).context("failed to decode JSON result from node bridge")?;
+        let flow = on_result(&mut result)?;
+        results.push(result);
+        if flow.is_break() {
+            let _ = child.kill();
+            let _ = child.wait();
+            linter.log.info(|| {
+                format!(
+                    "bridge stopped after {} of {} file(s)",
+                    results.len(),
+                    files.len()
+                )
+            });
+            return Ok(results);
+        }
+    }
+
+    let status = child
+        .wait()
+        .context("failed while waiting for node bridge process")?;
+    let stderr = stderr_reader.join().unwrap_or_default();
+    linter
+        .log
+        .info(|| format!("bridge finished in {:.2?}", started.elapsed()));
+    check_bridge_status(status, &stderr)?;
+    Ok(results)
+}
+
+fn node_bridge_command(linter: &LinterOptions, mode: &str) -> Result<Command> {
+    if linter.path.is_none() {
+        ensure_embedded_allowed(linter.allow_embedded)?;
+    }
+
+    let rule_options =
+        serde_json::to_string(&linter.rules).context("failed to serialize rule options")?;
+    linter.log.info(|| match &linter.path {
+        Some(path) => format!("runtime: node (linter '{}')", path.display()),
+        None => "runtime: node (embedded linter)".to_owned(),
+    });
+
+    let mut command = Command::new("node");
+    command
+        .args(&linter.node_args)
+        .arg("-e")
+        .arg(NODE_BRIDGE_SCRIPT)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .env("JULIETSCRIPT_LINT_OPTIONS", rule_options)
+        .env("JULIETSCRIPT_BRIDGE_MODE", mode);
+
+    if let Some(path) = &linter.path {
+        command.env("JULIETSCRIPT_LINTER_PATH", path);
+    } else if !EMBEDDED_LINTER_SOURCE.trim().is_empty() {
+        command.env("JULIETSCRIPT_LINTER_SOURCE", EMBEDDED_LINTER_SOURCE);
+    } else {
+        bail!("no linter source available. Provide --linter FILE or set JULIETSCRIPT_LINTER_PATH");
+    }
+    Ok(command)
+}
+
+/// Spawns the bridge process. Busy CI agents occasionally refuse a spawn for
+/// lack of resources, so those failures are retried; a missing `node` binary
+/// fails straight away.
+fn spawn_bridge(command: &mut Command, linter: &LinterOptions) -> Result<Child> {
+    retry_transient(linter.spawn_retries, linter.log, || spawn(command))
+        .context("failed to execute 'node'. Install Node.js (18+) to run julietscript-lint")
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Spawns on this thread that fail with `WouldBlock` before reaching the
+    /// OS. A real EAGAIN needs process limits that root ignores.
+    static FAILING_SPAWNS: std::cell::Cell<u32> = const { std::cell::Cell::new(0) };
+}
+
+fn spawn(command: &mut Command) -> io::Result<Child> {
+    #[cfg(test)]
+    if FAILING_SPAWNS.with(|failing| failing.replace(failing.get().saturating_sub(1))) > 0 {
+        return Err(io::Error::from(io::ErrorKind::WouldBlock));
+    }
+    command.spawn()
+}
+
+fn retry_transient<T>(
+    retries: u32,
+    log: Logger,
+    mut attempt: impl FnMut() -> io::Result<T>,
+) -> io::Result<T> {
+    let mut delay = SPAWN_RETRY_DELAY;
+    let mut retried = 0;
+    loop {
+        match attempt() {
+            Err(error) if retried < retries && is_transient_spawn_error(&error) => {
+                log.info(|| format!("spawning node failed ({error}); retrying in {delay:?}"));
+                std::thread::sleep(delay);
+                delay *= 2;
+                retried += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn is_transient_spawn_error(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::OutOfMemory | io::ErrorKind::Interrupted
+    )
+}
+
+fn send_payload(child: &mut Child, linter: &LinterOptions, files: &[LintInputFile]) -> Result<()> {
+    let payload = serde_json::to_vec(files).context("failed to serialize lint payload")?;
+    linter.log.debug(|| {
+        format!(
+            "payload: {} bytes for {} file(s)",
+            payload.len(),
+            files.len()
+        )
+    });
+
+    // Dropping stdin at the end of this function closes the pipe.
+    let mut stdin = child
+        .stdin
+        .take()
+        .context("failed to open stdin for node bridge process")?;
+    stdin
+        .write_all(&payload)
+        .context("failed to send lint payload to node bridge")
+}
+
+fn check_bridge_status(status: ExitStatus, stderr: &[u8]) -> Result<()> {
+    if status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(stderr);
+    let message = stderr.trim();
+    if message.is_empty() {
+        bail!("node bridge exited with status {status}");
+    }
+    bail!("node bridge exited with status {status}: {message}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_braces_handles_nested_empty_and_unbalanced_groups() {
+        assert_eq!(
+            expand_braces("scripts/**/*.{juliet,julietscript}"),
+            vec!["scripts/**/*.juliet", "scripts/**/*.julietscript"]
+        );
+        assert_eq!(
+            expand_braces("{a,b{c,d}}.jls"),
+            vec!["a.jls", "bc.jls", "bd.jls"]
+        );
+        assert_eq!(expand_braces("x{,y}"), vec!["x", "xy"]);
+        assert_eq!(expand_braces("{}/{a,b}"), vec!["{}/a", "{}/b"]);
+        assert_eq!(expand_braces("scripts/{a,b"), vec!["scripts/{a,b"]);
+        assert_eq!(
+            expand_braces("**/*.julietscript"),
+            vec!["**/*.julietscript"]
+        );
+    }
+
+    #[test]
+    fn parse_node_args_rejects_flags_that_replace_the_bridge() {
+        assert_eq!(
+            parse_node_args(" --no-warnings  --max-old-space-size=4096 --stack-size=2000 ")
+                .unwrap(),
+            vec![
+                "--no-warnings",
+                "--max-old-space-size=4096",
+                "--stack-size=2000"
+            ]
+        );
+        assert!(parse_node_args("").unwrap().is_empty());
+        for bad in [
+            "-e 1",
+            "--eval=process.exit(0)",
+            "-p",
+            "--require=./evil.js",
+            "evil.js",
+            "--max-old-space-size 4096",
+            "--max-old-space-size=lots",
+            "--no-warnings=1",
+            "--",
+            "--env-file=/tmp/envf",
+            "--check",
+            "--test",
+            "-v",
+            "--version",
+            "--help",
+            "--inspect-brk",
+        ] {
+            assert!(parse_node_args(bad).is_err(), "{bad} should be rejected");
+        }
+    }
+
+    #[test]
+    fn retry_transient_retries_only_transient_failures() {
+        let mut calls = 0;
+        let result = retry_transient(2, Logger::default(), || {
+            calls += 1;
+            if calls == 1 {
+                Err(io::Error::from(io::ErrorKind::WouldBlock))
+            } else {
+                Ok("spawned")
+            }
+        });
+        assert_eq!(result.unwrap(), "spawned");
+        assert_eq!(calls, 2);
+
+        let mut calls = 0;
+        let result: io::Result<()> = retry_transient(2, Logger::default(), || {
+            calls += 1;
+            Err(io::Error::from(io::ErrorKind::NotFound))
+        });
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+        assert_eq!(calls, 1);
+
+        let mut calls = 0;
+        let result: io::Result<()> = retry_transient(1, Logger::default(), || {
+            calls += 1;
+            Err(io::Error::from(io::ErrorKind::OutOfMemory))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn lint_directory_passes_spawn_retries_to_the_bridge() {
+        if Command::new("node").arg("--version").output().is_err() {
+            eprintln!("Skipping test: node is not available.");
+            return;
+        }
+
+        let root = std::env::temp_dir().join(format!(
+            "julietscript-lint-spawn-retries-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("a.julietscript"), "halt;\n").unwrap();
+        let options = |retries| LintOptions {
+            spawn_retries: Some(retries),
+            ..LintOptions::default()
+        };
+
+        FAILING_SPAWNS.with(|failing| failing.set(1));
+        let report = lint_directory(&root, &[], &options(1));
+        FAILING_SPAWNS.with(|failing| failing.set(1));
+        let no_retries = lint_directory(&root, &[], &options(0));
+        FAILING_SPAWNS.with(|failing| failing.set(0));
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            report.expect("the retried spawn should lint").summary.files,
+            1
+        );
+        let error = no_retries
+            .err()
+            .expect("--spawn-retries 0 should not retry");
+        assert!(
+            format!("{error:#}").contains("failed to execute 'node'"),
+            "{error:#}"
+        );
+    }
+
+    #[test]
+    fn normalize_windows_path_uses_forward_slashes() {
+        assert_eq!(
+            normalize_windows_path(r"\\?\C:\repo\scripts\bad.julietscript"),
+            "C:/repo/scripts/bad.julietscript"
+        );
+        assert_eq!(
+            normalize_windows_path(r"\\?\UNC\server\share\a.julietscript"),
+            "//server/share/a.julietscript"
+        );
+        assert_eq!(
+            normalize_windows_path("scripts/ok.julietscript"),
+            "scripts/ok.julietscript"
+        );
+    }
+}
@@ -16,6 +16,9 @@ const TOP_LEVEL_KEYWORDS = new Set([
   "halt"
 ]);
 
+// Declaration names that would read as statements or clash with future grammar.
+const RESERVED_NAMES = new Set(["juliet", "policy", "rubric", "cadence", "create", "halt", "extend", "using"]);
+
 const JULIET_ALLOWED_KEYS = new Set(["engine"]);
 const CREATE_ALLOWED_KEYS = new Map([
   ["preflight", "policy"],
@@ -24,6 +27,60 @@ const CREATE_ALLOWED_KEYS = new Map([
   ["rubric", "rubric"]
 ]);
 
+// The least strict `options.profile` that runs each rule. Rules not listed
+// here are "recommended". Under the default "recommended" profile the "strict"
+// rules are opt-in: they only run when enabled by rule ID.
+const PROFILES = ["minimal", "recommended", "strict"];
+const RULE_PROFILES = new Map([
+  ["syntax", "minimal"],
+  ["missing-semicolon", "minimal"],
+  ["numeric-literal", "minimal"],
+  ["unbalanced-delimiter", "minimal"],
+  ["trailing-comma", "minimal"],
+  ["identifier-name", "minimal"],
+  ["indentation", "strict"],
+  ["orphaned-artifact", "strict"],
+  ["max-line-length", "strict"],
+  ["engine-conflict", "strict"],
+  ["byte-order-mark", "strict"],
+  ["rubric-points", "strict"],
+  ["quote-style", "strict"],
+  ["duplicate-prompt", "strict"],
+  ["require-halt", "strict"],
+  ["missing-engine", "strict"],
+  ["search-budget", "strict"],
+  ["final-newline", "strict"]
+]);
+const DEFAULT_MAX_LINE_LENGTH = 120;
+const DEFAULT_MAX_SEARCH_BUDGET = 1000;
+
+const DECLARATION_KEYWORDS = new Map([
+  ["policy", "policy"],
+  ["rubric", "rubric"],
+  ["cadence", "cadence"],
+  ["create", "artifact"]
+]);
+
+function capitalize(text) {
+  return text.charAt(0).toUpperCase() + text.slice(1);
+}
+
+function collectDeclarations(tokens) {
+  const declarations = new Map();
+  for (let index = 0; index + 1 < tokens.length; index += 1) {
+    const keyword = tokens[index];
+    const name = tokens[index + 1];
+    if (keyword.kind !== "identifier" || name.kind !== "identifier") {
+      continue;
+    }
+    const type = DECLARATION_KEYWORDS.get(keyword.value);
+    if (type) {
+      declarations.set(`${type}:${name.value}`, name);
+    }
+  }
+  return declarations;
+}
+
 function comparePositions(a, b) {
   if (a.line !== b.line) {
     return a.line - b.line;
@@ -114,15 +171,32 @@ class Tokenizer {
     const start = this.getPosition();
     const startIndex = this.index;
     this.advance();
-    while (!this.isAtEnd() && this.isIdentifierPart(this.peek())) {
+    // Characters outside the name set, like the '-' in `my-artifact`, stay in
+    // the token so one focused error replaces a cascade of parse errors.
+    let invalidCharacter = null;
+    while (!this.isAtEnd() && (this.isIdentifierPart(this.peek()) || this.isStrayNameCharacter(this.peek()))) {
+      if (invalidCharacter === null && !this.isIdentifierPart(this.peek())) {
+        invalidCharacter = this.peek();
+      }
       this.advance();
     }
 
+    const value = this.source.slice(startIndex, this.index);
+    const end = this.getPosition();
+    if (invalidCharacter !== null) {
+      this.pushDiagnostic(
+        start,
+        end,
+        `Name '${value}' contains '${invalidCharacter}'; names may only use letters, digits, and '_'.`,
+        SEVERITY.ERROR,
+        "identifier-name"
+      );
+    }
     this.tokens.push({
       kind: "identifier",
-      value: this.source.slice(startIndex, this.index),
+      value,
       start,
-      end: this.getPosition()
+      end
     });
   }
 
@@ -133,6 +207,17 @@ class Tokenizer {
     while (!this.isAtEnd() && this.isDigit(this.peek())) {
       this.advance();
     }
+    // Keep malformed literals like `3.5` or `2abc` in one token so the parser
+    // can report them once instead of tripping over the leftovers.
+    while (!this.isAtEnd()) {
+      if (this.peek() === "." && this.isDigit(this.peek(1))) {
+        this.advance();
+      } else if (this.isIdentifierPart(this.peek())) {
+        this.advance();
+      } else {
+        break;
+      }
+    }
 
     this.tokens.push({
       kind: "number",
@@ -171,7 +256,8 @@ class Tokenizer {
       kind: "blockString",
       value: this.source.slice(contentStart),
       start,
-      end: this.getPosition()
+      end: this.getPosition(),
+      unterminated: true
     });
   }
 
@@ -260,36 +346,71 @@ class Tokenizer {
     return /[A-Za-z0-9_]/.test(ch);
   }
 
+  isStrayNameCharacter(ch) {
+    return !/\s/.test(ch) && !"{}[]()=;,.\"#".includes(ch);
+  }
+
   isDigit(ch) {
     return /[0-9]/.test(ch);
   }
 
-  pushDiagnostic(start, end, message, severity) {
+  pushDiagnostic(start, end, message, severity, rule = "syntax") {
     this.diagnostics.push({
       severity,
       message,
+      rule,
       range: { start, end }
     });
   }
 }
 
 class Parser {
-  constructor(tokens, initialDiagnostics) {
+  constructor(tokens, initialDiagnostics, options = {}) {
     this.tokens = tokens;
+    this.options = options;
     this.currentIndex = 0;
     this.diagnostics = [...initialDiagnostics];
+    this.declarations = collectDeclarations(tokens);
+    // Top-level statements in source order, for `parseJulietScript`. Each
+    // parse* method fills in the fields of `this.node` it understands.
+    this.statements = [];
+    this.node = null;
     this.context = {
       julietDeclared: false,
+      julietEngine: null,
+      firstStatement: null,
       globals: new Map(),
       policies: new Map(),
       rubrics: new Map(),
       cadences: new Map(),
-      artifacts: new Map()
+      artifacts: new Map(),
+      cadenceKeys: new Map(),
+      cadenceEngines: new Map(),
+      cadenceAttachments: [],
+      generationRuns: [],
+      rubricTotals: [],
+      searchBudgets: [],
+      prompts: new Map(),
+      rubricArtifacts: new Set(),
+      seedArtifacts: new Set(),
+      sourcePaths: [],
+      referencedCadences: new Set(),
+      artifactDependencies: [],
+      createdArtifacts: [],
+      terminalArtifacts: new Set(),
+      halted: false
     };
   }
 
   parse() {
     while (!this.isAtEnd()) {
+      if (!this.context.firstStatement) {
+        this.context.firstStatement = this.current();
+      }
+      const statementStart = this.current();
+      this.node = this.check("identifier") && TOP_LEVEL_KEYWORDS.has(statementStart.value)
+        ? { type: statementStart.value }
+        : null;
       if (this.matchKeyword("juliet")) {
         this.parseJuliet();
       } else if (this.matchKeyword("set")) {
@@ -312,8 +433,18 @@ class Parser {
         this.reportCurrent("Expected a top-level statement: juliet, set, policy, rubric, cadence, create, extend, or halt.", SEVERITY.ERROR);
         this.synchronizeTopLevel();
       }
+      if (this.node) {
+        this.node.range = { start: statementStart.start, end: this.previous().end };
+        this.statements.push(this.node);
+      }
     }
 
+    this.reportIncompleteCadences();
+    this.reportOrphanedArtifacts();
+    this.reportEngineConflicts();
+    this.reportMissingEngines();
+    this.reportMissingHalt();
+
     return this.diagnostics.sort((a, b) => {
       return comparePositions(a.range.start, b.range.start);
     });
@@ -321,11 +452,14 @@ class Parser {
 
   parseJuliet() {
     if (this.context.julietDeclared) {
-      this.reportPrevious("Duplicate juliet block. Only one top-level juliet block is expected.", SEVERITY.WARNING);
+      this.reportPrevious("Duplicate juliet block. Only one top-level juliet block is allowed.", SEVERITY.ERROR, "juliet-block");
+    } else if (this.previous() !== this.context.firstStatement) {
+      this.reportPrevious("The juliet block should be the first top-level block so its defaults apply to everything after it.", SEVERITY.WARNING, "juliet-block");
     }
     this.context.julietDeclared = true;
 
     this.expect("{", "Expected '{' after 'juliet'.");
+    this.node.settings = {};
     while (!this.check("}") && !this.isAtEnd()) {
       const key = this.expectIdentifier("Expected a key name in juliet block.");
       if (!key) {
@@ -334,14 +468,18 @@ class Parser {
       }
 
       if (!JULIET_ALLOWED_KEYS.has(key.value)) {
-        this.reportToken(key, `Unknown juliet key '${key.value}'. Supported keys: engine.`, SEVERITY.WARNING);
+        this.reportToken(key, `Unknown juliet key '${key.value}'. Supported keys: engine.`, SEVERITY.WARNING, "unknown-key");
       }
 
       this.expect("=", "Expected '=' after juliet key.");
+      const value = key.value === "engine"
+        ? this.expectEngineValue()
+        : this.expectValue("Expected a value after '='.");
       if (key.value === "engine") {
-        this.expectEngineValue();
-      } else {
-        this.expectValue("Expected a value after '='.");
+        this.context.julietEngine = value;
+      }
+      if (value) {
+        this.node.settings[key.value] = value.value;
       }
       this.expect(";", "Expected ';' after juliet assignment.");
     }
@@ -355,9 +493,22 @@ class Parser {
       return;
     }
     this.registerDefinition(this.context.policies, name, "policy");
+    this.node.name = name.value;
 
     this.expect("=", "Expected '=' after policy name.");
-    this.expectStringLiteral("Expected a string or triple-quoted block string for policy body.");
+    const body = this.expectStringLiteral("Expected a string or triple-quoted block string for policy body.");
+    if (body) {
+      this.node.body = body.value;
+      this.checkStringContent(body, "Policy body");
+      if (body.value.trim().length === 0) {
+        this.reportToken(
+          body,
+          `Policy '${name.value}' has an empty body and contributes nothing when attached.`,
+          SEVERITY.WARNING,
+          "empty-policy"
+        );
+      }
+    }
     this.expect(";", "Expected ';' after policy declaration.");
   }
 
@@ -370,44 +521,90 @@ class Parser {
     if (!key || !value) {
       return;
     }
+    this.node.key = key.value;
+    this.node.value = value.value;
 
     if (this.context.globals.has(key.value)) {
-      this.reportToken(key, `Duplicate global key assignment '${key.value}'.`, SEVERITY.WARNING);
+      this.reportToken(key, `Duplicate global key assignment '${key.value}'.`, SEVERITY.WARNING, "duplicate-global");
     }
     this.context.globals.set(key.value, value);
   }
 
   parseRubric() {
+    const keyword = this.previous();
     const name = this.expectIdentifier("Expected rubric name.");
     if (!name) {
       this.synchronizeTopLevel();
       return;
     }
     this.registerDefinition(this.context.rubrics, name, "rubric");
+    this.node.name = name.value;
+    this.node.criteria = [];
 
     this.expect("{", "Expected '{' after rubric name.");
     const criteria = new Set();
+    const criteriaByFoldedName = new Map();
     const tiebreakers = [];
+    let totalPoints = 0;
 
     while (!this.check("}") && !this.isAtEnd()) {
       if (this.matchKeyword("criterion")) {
+        const criterionKeyword = this.previous();
         const criterionLabel = this.expectStringLiteral("Expected criterion name string.");
         if (criterionLabel) {
+          this.checkCriterionName(criterionLabel, name, criteriaByFoldedName);
           criteria.add(criterionLabel.value);
         }
 
+        // `means` before `points` still parses, but is flagged so rubrics
+        // keep the canonical `points N means "..."` order.
+        let hasMeaning = false;
+        let criterionMeaning = null;
+        if (this.checkKeyword("means")) {
+          const meansKeyword = this.advance();
+          hasMeaning = true;
+          criterionMeaning = this.expectStringLiteral("Expected criterion meaning string after 'means'.");
+          const subject = criterionLabel ? `Criterion '${criterionLabel.value}'` : "Criterion";
+          this.reportToken(
+            meansKeyword,
+            `${subject} lists 'means' before 'points'; write points N means "..." instead.`,
+            SEVERITY.WARNING,
+            "criterion-order"
+          );
+        }
+
         this.expectKeyword("points", "Expected 'points' after criterion label.");
-        const points = this.expect("number", "Expected integer points value.");
+        const points = this.expectInteger("criterion points", "Expected integer points value.");
         if (points && Number.parseInt(points.value, 10) <= 0) {
-          this.reportToken(points, "Criterion points should be a positive integer.", SEVERITY.WARNING);
+          this.reportToken(points, "Criterion points should be a positive integer.", SEVERITY.WARNING, "criterion-points");
+        }
+        if (points) {
+          totalPoints += Number.parseInt(points.value, 10);
+        }
+        if (!hasMeaning && this.matchKeyword("means")) {
+          hasMeaning = true;
+          criterionMeaning = this.expectStringLiteral("Expected criterion meaning string after 'means'.");
         }
-        if (this.matchKeyword("means")) {
-          const criterionMeaning = this.expectStringLiteral("Expected criterion meaning string after 'means'.");
+        if (hasMeaning) {
           if (criterionMeaning && criterionMeaning.value.trim().length === 0) {
-            this.reportToken(criterionMeaning, "Criterion meaning should not be empty.", SEVERITY.WARNING);
+            this.reportToken(criterionMeaning, "Criterion meaning should not be empty.", SEVERITY.ERROR, "criterion-means");
           }
+        } else if (criterionLabel) {
+          this.reportToken(
+            criterionLabel,
+            `Criterion '${criterionLabel.value}' has no 'means' description; add means "..." so it can be scored consistently.`,
+            SEVERITY.WARNING,
+            "criterion-means"
+          );
+        } else {
+          this.reportToken(criterionKeyword, "Criterion has no 'means' description.", SEVERITY.WARNING, "criterion-means");
         }
         this.expect(";", "Expected ';' after criterion definition.");
+        this.node.criteria.push({
+          name: criterionLabel ? criterionLabel.value : null,
+          points: points ? Number.parseInt(points.value, 10) : null,
+          means: criterionMeaning ? criterionMeaning.value : null
+        });
         continue;
       }
 
@@ -419,7 +616,7 @@ class Parser {
             if (criterionName) {
               tiebreakers.push(criterionName);
             }
-            if (!this.match(",")) {
+            if (!this.matchListSeparator("tiebreakers")) {
               break;
             }
           }
@@ -433,37 +630,75 @@ class Parser {
       this.synchronizeInBlock();
     }
 
-    this.expect("}", "Expected '}' to close rubric block.");
+    const close = this.expect("}", "Expected '}' to close rubric block.");
+    this.node.tiebreakers = tiebreakers.map((tiebreaker) => tiebreaker.value);
+    this.context.rubricTotals.push({
+      name: name.value,
+      range: { start: keyword.start, end: (close || this.previous()).end },
+      totalPoints
+    });
 
     for (const tiebreaker of tiebreakers) {
       if (!criteria.has(tiebreaker.value)) {
         this.reportToken(
           tiebreaker,
           `Tiebreaker '${tiebreaker.value}' does not match any declared rubric criterion.`,
-          SEVERITY.WARNING
+          SEVERITY.WARNING,
+          "unknown-tiebreaker"
         );
       }
     }
   }
 
+  checkCriterionName(label, rubric, criteriaByFoldedName) {
+    const folded = label.value.toLowerCase();
+    const first = criteriaByFoldedName.get(folded);
+    if (!first) {
+      criteriaByFoldedName.set(folded, label);
+      return;
+    }
+    if (first.value === label.value) {
+      this.reportToken(
+        label,
+        `Duplicate criterion '${label.value}' in rubric '${rubric.value}'.`,
+        SEVERITY.WARNING,
+        "duplicate-criterion"
+      );
+      return;
+    }
+    this.reportToken(
+      label,
+      `Criterion '${label.value}' differs only in case from '${first.value}' in rubric '${rubric.value}'; spell it '${first.value}'.`,
+      SEVERITY.WARNING,
+      "criterion-case"
+    );
+  }
+
   parseCadence() {
+    const keyword = this.previous();
     const name = this.expectIdentifier("Expected cadence name.");
     if (!name) {
       this.synchronizeTopLevel();
       return;
     }
     this.registerDefinition(this.context.cadences, name, "cadence");
+    this.node.name = name.value;
 
     this.expect("{", "Expected '{' after cadence name.");
     let hasVariants = false;
     let hasSprints = false;
+    let hasCompare = false;
+    let variantCount = 0;
+    let sprintCount = 1;
+    let keepLimit = null;
 
     while (!this.check("}") && !this.isAtEnd()) {
       if (this.matchKeyword("compare")) {
+        hasCompare = true;
         this.expectKeyword("using", "Expected 'using' after 'compare'.");
         const rubricName = this.expectIdentifier("Expected rubric name after 'compare using'.");
         if (rubricName && !this.context.rubrics.has(rubricName.value)) {
-          this.reportToken(rubricName, `Unknown rubric '${rubricName.value}' in cadence compare action.`, SEVERITY.ERROR);
+          this.reportUnresolvedReference(rubricName, "rubric", `Unknown rubric '${rubricName.value}' in cadence compare action.`);
         }
         this.expect(";", "Expected ';' after compare statement.");
         continue;
@@ -471,9 +706,11 @@ class Parser {
 
       if (this.matchKeyword("keep")) {
         this.expectKeyword("best", "Expected 'best' after 'keep'.");
-        const keepCount = this.expect("number", "Expected integer keep limit after 'keep best'.");
+        const keepCount = this.expectInteger("'keep best'", "Expected integer keep limit after 'keep best'.");
         if (keepCount && Number.parseInt(keepCount.value, 10) <= 0) {
-          this.reportToken(keepCount, "'keep best' value should be greater than 0.", SEVERITY.ERROR);
+          this.reportToken(keepCount, "'keep best' value should be greater than 0.", SEVERITY.ERROR, "cadence-count");
+        } else if (keepCount) {
+          keepLimit = Number.parseInt(keepCount.value, 10);
         }
         this.expect(";", "Expected ';' after keep statement.");
         continue;
@@ -483,23 +720,32 @@ class Parser {
         const key = this.advance();
         this.advance(); // '='
         if (key.value === "engine") {
-          this.expectEngineValue();
+          const engine = this.expectEngineValue();
+          if (engine) {
+            this.context.cadenceEngines.set(name.value, engine);
+          }
         } else if (key.value === "variants" || key.value === "sprints") {
-          const value = this.expect("number", `Expected an integer for cadence key '${key.value}'.`);
+          const value = this.expectInteger(`cadence key '${key.value}'`, `Expected an integer for cadence key '${key.value}'.`);
           if (value && Number.parseInt(value.value, 10) <= 0) {
-            this.reportToken(value, `Cadence '${key.value}' should be greater than 0.`, SEVERITY.ERROR);
+            const consequence = key.value === "sprints"
+              ? "no iterations will run"
+              : "no candidate variants will be generated";
+            this.reportToken(key, `Cadence '${key.value}' is 0, so ${consequence}.`, SEVERITY.WARNING, "cadence-count");
           }
           if (key.value === "variants") {
             hasVariants = true;
+            variantCount = value ? Number.parseInt(value.value, 10) : 0;
           }
           if (key.value === "sprints") {
             hasSprints = true;
+            sprintCount = value ? Number.parseInt(value.value, 10) : 0;
           }
         } else {
           this.reportToken(
             key,
             `Unknown cadence key '${key.value}'. Supported keys: engine, variants, sprints.`,
-            SEVERITY.WARNING
+            SEVERITY.WARNING,
+            "unknown-key"
           );
           this.expectValue("Expected a value after cadence assignment.");
         }
@@ -511,42 +757,100 @@ class Parser {
       this.synchronizeInBlock();
     }
 
-    this.expect("}", "Expected '}' to close cadence block.");
-
-    if (!hasVariants) {
-      this.reportToken(name, "Cadence is missing required key 'variants'.", SEVERITY.WARNING);
-    }
-    if (!hasSprints) {
-      this.reportToken(name, "Cadence is missing required key 'sprints'.", SEVERITY.WARNING);
+    const close = this.expect("}", "Expected '}' to close cadence block.");
+    if (variantCount > 1 && !hasCompare) {
+      this.reportToken(
+        { start: keyword.start, end: (close || this.previous()).end },
+        `Cadence '${name.value}' generates ${variantCount} variants but has no 'compare using <rubric>;' to rank them.`,
+        SEVERITY.WARNING,
+        "cadence-missing-compare"
+      );
     }
+    this.context.searchBudgets.push({
+      name: name.value,
+      variants: variantCount,
+      sprints: sprintCount,
+      keep: keepLimit,
+      range: { start: keyword.start, end: (close || this.previous()).end }
+    });
+    this.context.cadenceKeys.set(name.value, { name, hasVariants, hasSprints });
   }
 
   parseCreate() {
+    const keyword = this.previous();
     const artifact = this.expectIdentifier("Expected artifact name after 'create'.");
     if (!artifact) {
       this.synchronizeTopLevel();
       return;
     }
 
+    this.node.name = artifact.value;
     this.expectKeyword("from", "Expected 'from' after artifact name.");
-    if (this.matchKeyword("juliet")) {
-      this.expectStringLiteral("Expected prompt string after 'from juliet'.");
+    let seededFromFiles = false;
+    const generated = this.matchKeyword("juliet");
+    if (generated) {
+      const prompt = this.expectStringLiteral("Expected prompt string after 'from juliet'.");
+      if (prompt) {
+        this.node.prompt = prompt.value;
+        this.checkStringContent(prompt, "Prompt");
+        this.checkDuplicatePrompt(artifact, prompt);
+      }
     } else if (this.matchKeyword("julietArtifactSourceFiles")) {
+      seededFromFiles = true;
       this.parseCreateSourceFilesList();
     } else {
       this.reportCurrent("Expected 'juliet' or 'julietArtifactSourceFiles' after 'from'.", SEVERITY.ERROR);
     }
 
+    let dependencies = [];
     if (this.matchKeyword("using")) {
-      this.parseCreateUsingList();
+      dependencies = this.parseCreateUsingList();
     }
 
     if (this.matchKeyword("with")) {
-      this.parseCreateAttachments();
+      if (seededFromFiles) {
+        this.reportToken(
+          this.previous(),
+          `Artifact '${artifact.value}' is seeded from julietArtifactSourceFiles and has no generation run, so its 'with' attachments have no effect.`,
+          SEVERITY.WARNING,
+          "seeded-attachments"
+        );
+      }
+      this.parseCreateAttachments(artifact);
     }
 
     this.expect(";", "Expected ';' after create statement.");
+    if (seededFromFiles) {
+      this.context.seedArtifacts.add(artifact.value);
+    }
+    if (generated) {
+      this.context.generationRuns.push({ artifact, range: { start: keyword.start, end: this.previous().end } });
+    }
+    this.node.using = dependencies;
     this.registerDefinition(this.context.artifacts, artifact, "artifact");
+    this.context.artifactDependencies.push({ name: artifact.value, using: dependencies });
+    this.context.createdArtifacts.push(artifact);
+  }
+
+  // Prompts are compared after dedenting and collapsing whitespace, so a copy
+  // that was only reflowed or re-indented still counts as a duplicate.
+  checkDuplicatePrompt(artifact, prompt) {
+    const text = prompt.kind === "blockString" ? dedentBlockString(prompt.value) : prompt.value;
+    const normalized = text.replace(/\s+/g, " ").trim();
+    if (normalized.length === 0) {
+      return;
+    }
+    const first = this.context.prompts.get(normalized);
+    if (first) {
+      this.reportToken(
+        prompt,
+        `Artifact '${artifact.value}' has the same prompt as '${first.value}'; did you mean to edit one of them?`,
+        SEVERITY.WARNING,
+        "duplicate-prompt"
+      );
+      return;
+    }
+    this.context.prompts.set(normalized, artifact);
   }
 
   parseCreateSourceFilesList() {
@@ -556,15 +860,29 @@ class Parser {
 
     if (!this.check("]")) {
       while (true) {
+        const separator = this.previous();
         const sourcePath = this.expect("string", "Expected quoted file path in source files list.");
         if (sourcePath) {
           pathCount += 1;
-          if (seenPaths.has(sourcePath.value)) {
-            this.reportToken(sourcePath, `Duplicate source file path '${sourcePath.value}' in julietArtifactSourceFiles list.`, SEVERITY.WARNING);
+          this.context.sourcePaths.push({
+            path: sourcePath.value,
+            range: { start: sourcePath.start, end: sourcePath.end }
+          });
+          // "./a.md" and "a.md" name the same file.
+          const normalizedPath = sourcePath.value.replace(/^(\.\/)+/, "");
+          if (seenPaths.has(normalizedPath)) {
+            // A repeat is never first in the list, so `separator` is its comma.
+            this.reportToken(
+              sourcePath,
+              `Duplicate source file path '${sourcePath.value}' in julietArtifactSourceFiles list.`,
+              SEVERITY.WARNING,
+              "duplicate-source-path",
+              { range: { start: separator.start, end: sourcePath.end }, text: "" }
+            );
           }
-          seenPaths.add(sourcePath.value);
+          seenPaths.add(normalizedPath);
         }
-        if (!this.match(",")) {
+        if (!this.matchListSeparator("julietArtifactSourceFiles")) {
           break;
         }
       }
@@ -576,30 +894,45 @@ class Parser {
       this.reportToken(
         listStart || this.previous(),
         "Expected at least one file path in julietArtifactSourceFiles list.",
-        SEVERITY.ERROR
+        SEVERITY.ERROR,
+        "empty-source-list"
       );
     }
   }
 
   parseCreateUsingList() {
+    const dependencies = [];
     this.expect("[", "Expected '[' after 'using'.");
     if (!this.check("]")) {
       while (true) {
         const dependency = this.expectIdentifier("Expected artifact name in 'using' list.");
         if (dependency && !this.context.artifacts.has(dependency.value)) {
-          this.reportToken(dependency, `Unknown artifact '${dependency.value}' in using list.`, SEVERITY.ERROR);
+          this.reportUsingDependency(dependency);
+        }
+        if (dependency) {
+          dependencies.push(dependency.value);
         }
-        if (!this.match(",")) {
+        if (!this.matchListSeparator("using")) {
           break;
         }
       }
     }
     this.expect("]", "Expected ']' after using list.");
+    return dependencies;
   }
 
-  parseCreateAttachments() {
+  parseCreateAttachments(artifact) {
+    const withKeyword = this.previous();
     this.expect("{", "Expected '{' to begin create attachments block.");
     const seenKeys = new Set();
+    if (this.check("}")) {
+      this.reportToken(
+        withKeyword,
+        `Artifact '${artifact.value}' has an empty 'with { }' block; remove it or add attachments.`,
+        SEVERITY.WARNING,
+        "empty-attachments"
+      );
+    }
 
     while (!this.check("}") && !this.isAtEnd()) {
       const key = this.expectIdentifier("Expected attachment key in create with-block.");
@@ -608,19 +941,26 @@ class Parser {
         continue;
       }
       if (seenKeys.has(key.value)) {
-        this.reportToken(key, `Duplicate create attachment '${key.value}'.`, SEVERITY.WARNING);
+        this.reportToken(key, `Duplicate create attachment '${key.value}'.`, SEVERITY.WARNING, "duplicate-attachment");
       }
       seenKeys.add(key.value);
+      if (key.value === "rubric") {
+        this.context.rubricArtifacts.add(artifact.value);
+      }
 
       this.expect("=", "Expected '=' after create attachment key.");
       const value = this.expectIdentifier("Expected reference name after '='.");
       this.expect(";", "Expected ';' after create attachment.");
+      if (value) {
+        this.node.attachments = { ...this.node.attachments, [key.value]: value.value };
+      }
 
       if (!CREATE_ALLOWED_KEYS.has(key.value)) {
         this.reportToken(
           key,
           `Unknown create attachment key '${key.value}'. Supported keys: preflight, failureTriage, cadence, rubric.`,
-          SEVERITY.WARNING
+          SEVERITY.WARNING,
+          "unknown-key"
         );
         continue;
       }
@@ -632,7 +972,10 @@ class Parser {
       const type = CREATE_ALLOWED_KEYS.get(key.value);
       const exists = this.definitionExists(type, value.value);
       if (!exists) {
-        this.reportToken(value, `Unknown ${type} '${value.value}' referenced by '${key.value}'.`, SEVERITY.ERROR);
+        this.reportUnresolvedReference(value, type, `Unknown ${type} '${value.value}' referenced by '${key.value}'.`);
+      } else if (type === "cadence") {
+        this.context.referencedCadences.add(value.value);
+        this.context.cadenceAttachments.push({ artifact, cadence: value });
       }
     }
 
@@ -640,40 +983,252 @@ class Parser {
   }
 
   parseExtend() {
+    const keyword = this.previous();
     const artifact = this.expectIdentifier("Expected artifact name after 'extend'.");
     if (!artifact) {
       this.synchronizeTopLevel();
       return;
     }
     if (!this.context.artifacts.has(artifact.value)) {
-      this.reportToken(artifact, `Unknown artifact '${artifact.value}' in extend statement.`, SEVERITY.ERROR);
+      this.reportUnresolvedReference(artifact, "artifact", `Unknown artifact '${artifact.value}' in extend statement.`);
     }
 
+    this.node.artifact = artifact.value;
     this.expect(".", "Expected '.' after artifact name in extend target.");
     const target = this.expectIdentifier("Expected extend target after '.'.");
+    if (target) {
+      this.node.target = target.value;
+    }
+    // Seeds are never scored; that gets its own warning once the statement ends.
+    const extendsSeed = Boolean(target) && target.value === "rubric" && this.context.seedArtifacts.has(artifact.value);
     if (target && target.value !== "rubric") {
-      this.reportToken(target, "Only '<Artifact>.rubric' is currently supported by extend.", SEVERITY.ERROR);
+      this.reportToken(target, "Only '<Artifact>.rubric' is currently supported by extend.", SEVERITY.ERROR, "extend-target");
+    } else if (target && !extendsSeed && this.context.artifacts.has(artifact.value) && !this.context.rubricArtifacts.has(artifact.value)) {
+      this.reportToken(
+        target,
+        `Artifact '${artifact.value}' has no 'rubric' attachment, so there is no rubric to extend.`,
+        SEVERITY.WARNING,
+        "extend-without-rubric"
+      );
     }
 
     this.expectKeyword("with", "Expected 'with' after extend target.");
-    this.expectStringLiteral("Expected string or block string after 'with'.");
+    const guidance = this.expectStringLiteral("Expected string or block string after 'with'.");
+    if (guidance) {
+      this.node.guidance = guidance.value;
+    }
+    if (guidance && guidance.value.trim().length === 0) {
+      this.reportToken(guidance, "Extend guidance should not be empty.", SEVERITY.WARNING, "empty-extend-guidance");
+    }
     this.expect(";", "Expected ';' after extend statement.");
+    if (extendsSeed) {
+      this.reportToken(
+        { start: keyword.start, end: this.previous().end },
+        `Artifact '${artifact.value}' is seeded from julietArtifactSourceFiles and is never scored, so it has no rubric to extend.`,
+        SEVERITY.WARNING,
+        "extend-seed-rubric"
+      );
+    }
   }
 
   parseHalt() {
+    this.context.halted = true;
+    const created = this.context.createdArtifacts;
+    if (created.length > 0) {
+      this.context.terminalArtifacts.add(created[created.length - 1].value);
+    }
     if (!this.check(";")) {
-      this.expectStringLiteral("Expected optional halt message string before ';'.");
+      const message = this.expectStringLiteral("Expected optional halt message string before ';'.");
+      if (message) {
+        this.node.message = message.value;
+      }
+      if (message && message.value.trim().length === 0) {
+        this.reportToken(
+          message,
+          "Halt message should not be empty; add a reason or use a bare 'halt;'.",
+          SEVERITY.WARNING,
+          "empty-halt-message"
+        );
+      }
     }
     this.expect(";", "Expected ';' after halt statement.");
   }
 
+  reportIncompleteCadences() {
+    // Missing keys only matter once a create actually runs the cadence.
+    for (const cadenceName of this.context.referencedCadences) {
+      const cadence = this.context.cadenceKeys.get(cadenceName);
+      if (!cadence) {
+        continue;
+      }
+      if (!cadence.hasVariants) {
+        this.reportToken(cadence.name, "Cadence is missing required key 'variants'.", SEVERITY.WARNING, "cadence-missing-key");
+      }
+      if (!cadence.hasSprints) {
+        this.reportToken(cadence.name, "Cadence is missing required key 'sprints'.", SEVERITY.WARNING, "cadence-missing-key");
+      }
+    }
+  }
+
+  checkStringContent(token, label) {
+    // Text pasted from documents or terminals can carry invisible characters
+    // that break prompt consumption downstream. Report only the first one.
+    let line = token.start.line;
+    let character = token.start.character + (token.kind === "blockString" ? 3 : 1);
+    for (let index = 0; index < token.value.length; index += 1) {
+      const ch = token.value[index];
+      const code = ch.charCodeAt(0);
+      let message = null;
+      if (index === 0 && ch === "\uFEFF") {
+        message = `${label} starts with a byte order mark (U+FEFF).`;
+      } else if (ch !== "\t" && ch !== "\n" && ch !== "\r" && (code < 0x20 || (code >= 0x7f && code <= 0x9f))) {
+        const codePoint = code.toString(16).toUpperCase().padStart(4, "0");
+        message = `${label} contains control character U+${codePoint}.`;
+      }
+      if (message) {
+        this.diagnostics.push({
+          severity: SEVERITY.WARNING,
+          message,
+          rule: "control-character",
+          range: {
+            start: { line, character },
+            end: { line, character: character + 1 }
+          }
+        });
+        return;
+      }
+      if (ch === "\n") {
+        line += 1;
+        character = 0;
+      } else {
+        character += 1;
+      }
+    }
+  }
+
+  reportMissingHalt() {
+    if (this.context.halted) {
+      return;
+    }
+    this.reportToken(
+      this.current(),
+      "Script has no 'halt' statement; end it with 'halt;' so termination is explicit.",
+      SEVERITY.WARNING,
+      "require-halt"
+    );
+  }
+
+  reportOrphanedArtifacts() {
+    // The last artifact overall, and the last one before each halt, are
+    // plausible final outputs. Anything else should feed a later `using`.
+    const created = this.context.createdArtifacts;
+    if (created.length > 0) {
+      this.context.terminalArtifacts.add(created[created.length - 1].value);
+    }
+    const consumed = new Set(this.context.artifactDependencies.flatMap((artifact) => artifact.using));
+    for (const artifact of created) {
+      if (!consumed.has(artifact.value) && !this.context.terminalArtifacts.has(artifact.value)) {
+        this.reportToken(
+          artifact,
+          `Artifact '${artifact.value}' is never used by a later create and is not a final output; did you forget to add it to a 'using' list?`,
+          SEVERITY.WARNING,
+          "orphaned-artifact"
+        );
+      }
+    }
+  }
+
+  // A generation run needs an engine from the juliet block or from its cadence;
+  // creates have no engine key of their own.
+  reportMissingEngines() {
+    if (this.context.julietEngine) {
+      return;
+    }
+    for (const { artifact, range } of this.context.generationRuns) {
+      const attachment = this.context.cadenceAttachments.find((entry) => entry.artifact === artifact);
+      if (attachment && this.context.cadenceEngines.has(attachment.cadence.value)) {
+        continue;
+      }
+      this.reportToken(
+        range,
+        `Artifact '${artifact.value}' is generated from juliet but no engine is declared; set 'engine' in the juliet block or in its cadence.`,
+        SEVERITY.WARNING,
+        "missing-engine"
+      );
+    }
+  }
+
+  reportEngineConflicts() {
+    const julietEngine = this.context.julietEngine;
+    if (!julietEngine) {
+      return;
+    }
+    for (const { artifact, cadence } of this.context.cadenceAttachments) {
+      const cadenceEngine = this.context.cadenceEngines.get(cadence.value);
+      if (cadenceEngine && cadenceEngine.value !== julietEngine.value) {
+        this.reportToken(
+          cadence,
+          `Artifact '${artifact.value}' runs on engine '${cadenceEngine.value}' from cadence '${cadence.value}', not the juliet default '${julietEngine.value}'. A cadence's engine takes precedence over the juliet block.`,
+          SEVERITY.WARNING,
+          "engine-conflict"
+        );
+      }
+    }
+  }
+
   registerDefinition(map, token, label) {
+    if (RESERVED_NAMES.has(token.value)) {
+      this.reportToken(
+        token,
+        `${capitalize(label)} name '${token.value}' is a reserved keyword; choose a different name.`,
+        SEVERITY.ERROR,
+        "reserved-name"
+      );
+    }
     if (map.has(token.value)) {
-      this.reportToken(token, `Duplicate ${label} '${token.value}'.`, SEVERITY.WARNING);
+      this.reportToken(token, `Duplicate ${label} '${token.value}'.`, SEVERITY.WARNING, "duplicate-definition");
     }
     map.set(token.value, token);
   }
 
+  reportUnresolvedReference(token, type, unknownMessage) {
+    // Execution is top-to-bottom, so a name declared further down is not yet
+    // available here. Say so instead of calling it unknown.
+    const declaration = this.declarations.get(`${type}:${token.value}`);
+    if (declaration && comparePositions(declaration.start, token.start) > 0) {
+      this.reportToken(
+        token,
+        `${capitalize(type)} '${token.value}' is referenced before it is declared on line ${declaration.start.line + 1}.`,
+        SEVERITY.ERROR,
+        "forward-reference"
+      );
+      return;
+    }
+    // A buffer linted on its own may use artifacts created by other files.
+    if (type === "artifact" && this.options.externalArtifacts) {
+      return;
+    }
+    this.reportToken(token, unknownMessage, SEVERITY.ERROR, "unknown-reference");
+  }
+
+  reportUsingDependency(dependency) {
+    // Naming a policy, rubric, or cadence here is a category mistake rather
+    // than a typo, so say what the name actually is.
+    const otherType = this.declarations.has(`artifact:${dependency.value}`)
+      ? null
+      : ["policy", "rubric", "cadence"].find((type) => this.declarations.has(`${type}:${dependency.value}`));
+    if (otherType) {
+      this.reportToken(
+        dependency,
+        `'${dependency.value}' is a ${otherType}, not an artifact; 'using' only accepts artifacts made by 'create'.`,
+        SEVERITY.ERROR,
+        "using-non-artifact"
+      );
+      return;
+    }
+    this.reportUnresolvedReference(dependency, "artifact", `Unknown artifact '${dependency.value}' in using list.`);
+  }
+
   definitionExists(type, name) {
     if (type === "policy") {
       return this.context.policies.has(name);
@@ -692,10 +1247,10 @@ class Parser {
 
   expectEngineValue() {
     if (this.check("identifier") || this.check("string")) {
-      this.advance();
-      return;
+      return this.advance();
     }
     this.reportCurrent("Expected engine value as an identifier or quoted string.", SEVERITY.ERROR);
+    return null;
   }
 
   expectValue(message) {
@@ -706,6 +1261,35 @@ class Parser {
     return null;
   }
 
+  expectInteger(label, missingMessage) {
+    // A quoted number is consumed so the rest of the statement still parses.
+    if (this.check("string")) {
+      const token = this.advance();
+      this.reportToken(
+        token,
+        `Expected an integer for ${label}, found the quoted string "${token.value}"; remove the quotes.`,
+        SEVERITY.ERROR,
+        "quoted-number"
+      );
+      return null;
+    }
+    if (!this.check("number")) {
+      this.reportCurrent(missingMessage, SEVERITY.ERROR);
+      return null;
+    }
+    const token = this.advance();
+    if (!/^[0-9]+$/.test(token.value)) {
+      this.reportToken(
+        token,
+        `Expected an integer for ${label}, found '${token.value}'.`,
+        SEVERITY.ERROR,
+        "numeric-literal"
+      );
+      return null;
+    }
+    return token;
+  }
+
   expectStringLiteral(message) {
     if (this.check("string") || this.check("blockString")) {
       return this.advance();
@@ -718,6 +1302,18 @@ class Parser {
     if (this.check("identifier")) {
       return this.advance();
     }
+    // The tokenizer reads `2brief` as a malformed number; where a name is
+    // expected it is a name that starts with a digit.
+    if (this.check("number") && /^[0-9]+[A-Za-z_]/.test(this.current().value)) {
+      const token = this.advance();
+      this.reportToken(
+        token,
+        `Name '${token.value}' starts with '${token.value.charAt(0)}'; names must start with a letter.`,
+        SEVERITY.ERROR,
+        "identifier-name"
+      );
+      return { ...token, kind: "identifier" };
+    }
     this.reportCurrent(message, SEVERITY.ERROR);
     return null;
   }
@@ -734,10 +1330,22 @@ class Parser {
     if (this.check(kind)) {
       return this.advance();
     }
-    this.reportCurrent(message, SEVERITY.ERROR);
+    if (kind === ";" && this.currentIndex > 0) {
+      this.reportCurrent(message, SEVERITY.ERROR, "missing-semicolon", this.insertAfterPrevious(";"));
+    } else {
+      this.reportCurrent(message, SEVERITY.ERROR);
+    }
     return null;
   }
 
+  insertAfterPrevious(text) {
+    const position = this.previous().end;
+    return {
+      range: { start: position, end: position },
+      text
+    };
+  }
+
   synchronizeTopLevel() {
     while (!this.isAtEnd()) {
       if (this.check(";")) {
@@ -760,23 +1368,28 @@ class Parser {
     }
   }
 
-  reportCurrent(message, severity) {
-    this.reportToken(this.current(), message, severity);
+  reportCurrent(message, severity, rule = "syntax", fix = undefined) {
+    this.reportToken(this.current(), message, severity, rule, fix);
   }
 
-  reportPrevious(message, severity) {
-    this.reportToken(this.previous(), message, severity);
+  reportPrevious(message, severity, rule = "syntax") {
+    this.reportToken(this.previous(), message, severity, rule);
   }
 
-  reportToken(token, message, severity) {
-    this.diagnostics.push({
+  reportToken(token, message, severity, rule = "syntax", fix = undefined) {
+    const diagnostic = {
       severity,
       message,
+      rule,
       range: {
         start: token.start,
         end: token.end
       }
-    });
+    };
+    if (fix) {
+      diagnostic.fix = fix;
+    }
+    this.diagnostics.push(diagnostic);
   }
 
   check(kind) {
@@ -795,6 +1408,24 @@ class Parser {
     return false;
   }
 
+  // Consumes a list comma. A comma directly before the closing ']' is reported
+  // once at the comma, so the list still closes cleanly instead of cascading
+  // into "expected item" errors.
+  matchListSeparator(listName) {
+    if (!this.match(",")) {
+      return false;
+    }
+    if (!this.check("]")) {
+      return true;
+    }
+    const comma = this.previous();
+    this.reportToken(comma, `Trailing comma is not allowed in ${listName} list.`, SEVERITY.ERROR, "trailing-comma", {
+      range: { start: comma.start, end: comma.end },
+      text: ""
+    });
+    return false;
+  }
+
   checkKeyword(keyword) {
     return this.check("identifier") && this.current().value === keyword;
   }
@@ -831,14 +1462,411 @@ class Parser {
   }
 }
 
-function lintJulietScript(source) {
+function checkIndentation(source, tokens) {
+  const lines = source.split("\n");
+  const diagnostics = [];
+  const blocks = [];
+  let previousLine = -1;
+
+  const report = (line, indent, message) => {
+    diagnostics.push({
+      severity: SEVERITY.WARNING,
+      message,
+      rule: "indentation",
+      range: {
+        start: { line, character: 0 },
+        end: { line, character: indent.length }
+      }
+    });
+  };
+
+  for (const token of tokens) {
+    if (token.kind === "eof") {
+      break;
+    }
+    if ((token.kind === "}" || token.kind === "]") && blocks.length > 0) {
+      blocks.pop();
+    }
+
+    const firstOnLine = token.start.line !== previousLine;
+    previousLine = token.end.line;
+    const block = blocks[blocks.length - 1];
+    if (firstOnLine && block) {
+      const indent = lines[token.start.line].match(/^[ \t]*/)[0];
+      const hasTabs = indent.includes("\t");
+      const hasSpaces = indent.includes(" ");
+      const style = hasTabs ? "tabs" : "spaces";
+
+      if (hasTabs && hasSpaces) {
+        if (!block.mixedReported) {
+          report(token.start.line, indent, "Indentation mixes tabs and spaces on the same line.");
+          block.mixedReported = true;
+        }
+      } else if (indent.length > 0) {
+        if (!block.style) {
+          block.style = style;
+        } else if (block.style !== style && !block.mixedReported) {
+          report(
+            token.start.line,
+            indent,
+            `Block mixes tabs and spaces: this line is indented with ${style}, earlier lines with ${block.style}.`
+          );
+          block.mixedReported = true;
+        }
+        if (!hasTabs && indent.length % 2 !== 0) {
+          report(token.start.line, indent, `Indentation should be a multiple of two spaces, found ${indent.length}.`);
+        }
+      }
+    }
+
+    if (token.kind === "{" || token.kind === "[") {
+      blocks.push({ style: null, mixedReported: false });
+    }
+  }
+
+  return diagnostics;
+}
+
+function leadingWhitespace(line) {
+  return line.match(/^[ \t]*/)[0].length;
+}
+
+const DELIMITER_PAIRS = new Map([
+  ["{", { close: "}", label: "block" }],
+  ["[", { close: "]", label: "list" }],
+  ["(", { close: ")", label: "group" }]
+]);
+const CLOSING_DELIMITERS = new Map([...DELIMITER_PAIRS].map(([open, { close }]) => [close, open]));
+
+// Finds the first delimiter that cannot be paired, reported in place of the
+// parser's output.
+function findUnbalancedDelimiter(tokens) {
+  const open = [];
+  const unmatched = (token, message) => ({
+    severity: SEVERITY.ERROR,
+    message,
+    rule: "unbalanced-delimiter",
+    range: { start: token.start, end: token.end }
+  });
+
+  for (const token of tokens) {
+    if (DELIMITER_PAIRS.has(token.kind)) {
+      open.push(token);
+      continue;
+    }
+    const opener = CLOSING_DELIMITERS.get(token.kind);
+    if (!opener) {
+      continue;
+    }
+    const innermost = open.pop();
+    if (!innermost) {
+      return unmatched(token, `Unmatched '${token.kind}' with no open '${opener}'.`);
+    }
+    if (innermost.kind !== opener) {
+      const { close, label } = DELIMITER_PAIRS.get(innermost.kind);
+      return unmatched(innermost, `Unmatched '${innermost.kind}' for ${label} opened here; found '${token.kind}' before its closing '${close}'.`);
+    }
+  }
+
+  if (open.length > 0) {
+    const innermost = open[open.length - 1];
+    return unmatched(innermost, `Unmatched '${innermost.kind}' for ${DELIMITER_PAIRS.get(innermost.kind).label} opened here.`);
+  }
+  return null;
+}
+
+function checkRubricPoints(rubricTotals, options) {
+  const diagnostics = [];
+  for (const rubric of rubricTotals) {
+    if (Number.isInteger(options.exactRubricPoints)) {
+      if (rubric.totalPoints !== options.exactRubricPoints) {
+        diagnostics.push({
+          severity: SEVERITY.ERROR,
+          message: `Rubric '${rubric.name}' criteria total ${rubric.totalPoints} points; rubrics must total exactly ${options.exactRubricPoints}.`,
+          rule: "rubric-points",
+          range: rubric.range
+        });
+      }
+    } else if (Number.isInteger(options.maxRubricPoints) && rubric.totalPoints > options.maxRubricPoints) {
+      diagnostics.push({
+        severity: SEVERITY.WARNING,
+        message: `Rubric '${rubric.name}' criteria total ${rubric.totalPoints} points, more than the maximum of ${options.maxRubricPoints}.`,
+        rule: "rubric-points",
+        range: rubric.range
+      });
+    }
+  }
+  return diagnostics;
+}
+
+// Follows the spec's branching note: each sprint branches every survivor into
+// `variants` candidates, and `keep best` caps the survivors carried forward.
+// Without a cap every candidate survives, so the count grows as variants^sprints.
+function countCadenceCandidates(variants, sprints, keep) {
+  let total = 0;
+  let survivors = 1;
+  for (let sprint = 1; sprint <= sprints; sprint += 1) {
+    const candidates = survivors * variants;
+    const next = keep === null ? candidates : Math.min(keep, candidates);
+    if (next === survivors) {
+      // Every remaining sprint now generates the same number of candidates.
+      return total + candidates * (sprints - sprint + 1);
+    }
+    total += candidates;
+    survivors = next;
+    if (total > Number.MAX_SAFE_INTEGER) {
+      return Infinity;
+    }
+  }
+  return total;
+}
+
+function checkSearchBudgets(searchBudgets, limit) {
+  const diagnostics = [];
+  for (const cadence of searchBudgets) {
+    const candidates = countCadenceCandidates(cadence.variants, cadence.sprints, cadence.keep);
+    if (candidates <= limit) {
+      continue;
+    }
+    const count = Number.isFinite(candidates) ? `${candidates}` : `more than ${Number.MAX_SAFE_INTEGER}`;
+    diagnostics.push({
+      severity: SEVERITY.WARNING,
+      message: `Cadence '${cadence.name}' generates ${count} candidates over ${cadence.sprints} sprint(s), more than the search budget of ${limit}.`,
+      rule: "search-budget",
+      range: cadence.range
+    });
+  }
+  return diagnostics;
+}
+
+// A file should end with exactly one newline. The fix replaces whatever
+// follows the last non-blank line, nothing or a run of blank lines, with one.
+function checkFinalNewline(source) {
+  const content = source.replace(/[\r\n]+$/, "");
+  if (content.length === 0) {
+    return [];
+  }
+  const newline = source.includes("\r\n") ? "\r\n" : "\n";
+  const trailing = source.slice(content.length);
+  if (trailing === newline) {
+    return [];
+  }
+  const positionAt = (offset) => {
+    const before = source.slice(0, offset).split("\n");
+    return { line: before.length - 1, character: before[before.length - 1].length };
+  };
+  const start = positionAt(content.length);
+  const end = positionAt(source.length);
+  return [{
+    severity: SEVERITY.WARNING,
+    message: trailing.length === 0
+      ? "File does not end with a newline."
+      : "File ends with more than one newline.",
+    rule: "final-newline",
+    range: { start, end },
+    fix: { range: { start, end }, text: newline }
+  }];
+}
+
+function checkQuoteStyle(tokens) {
+  // Multi-line plain strings are already a tokenizer error, so only the
+  // reverse case needs a rule: triple quotes around a single line.
+  const diagnostics = [];
+  for (const token of tokens) {
+    if (token.kind !== "blockString" || token.unterminated || token.value.includes("\n")) {
+      continue;
+    }
+    const diagnostic = {
+      severity: SEVERITY.WARNING,
+      message: "Single-line string uses triple quotes; use plain quotes instead.",
+      rule: "quote-style",
+      range: { start: token.start, end: token.end }
+    };
+    // Quotes and backslashes would need escaping in a plain string, so those
+    // are left for the author to rewrite.
+    if (!/["\\]/.test(token.value)) {
+      diagnostic.fix = { range: { start: token.start, end: token.end }, text: `"${token.value}"` };
+    }
+    diagnostics.push(diagnostic);
+  }
+  return diagnostics;
+}
+
+function checkBlockStringIndentation(tokens) {
+  // Consumers dedent block strings by their common indentation. When the
+  // first content line is indented but a later one is not, that common
+  // indentation collapses to nothing and every line keeps its raw indent.
+  const diagnostics = [];
+  for (const token of tokens) {
+    if (token.kind !== "blockString") {
+      continue;
+    }
+    const lines = token.value.split("\n");
+    let expected = null;
+    for (let index = 1; index < lines.length; index += 1) {
+      const text = lines[index].replace(/\r$/, "");
+      if (text.trim() === "") {
+        continue;
+      }
+      const indent = leadingWhitespace(text);
+      if (expected === null) {
+        expected = indent;
+        if (expected === 0) {
+          break;
+        }
+        continue;
+      }
+      if (indent < expected) {
+        diagnostics.push({
+          severity: SEVERITY.WARNING,
+          message: `Block string line is indented less than its first line (${indent} vs ${expected}), so the block cannot be dedented consistently.`,
+          rule: "block-string-indentation",
+          range: {
+            start: { line: token.start.line + index, character: indent },
+            end: { line: token.start.line + index, character: text.length }
+          }
+        });
+        break;
+      }
+    }
+  }
+  return diagnostics;
+}
+
+// Formatter companion to `block-string-indentation`: removes the indentation
+// shared by every non-blank line after the opening quotes.
+function dedentBlockString(value) {
+  const lines = value.split("\n");
+  const indents = lines
+    .slice(1)
+    .filter((line) => line.trim() !== "")
+    .map(leadingWhitespace);
+  if (indents.length === 0) {
+    return value;
+  }
+  const common = Math.min(...indents);
+  return lines
+    .map((line, index) => (index === 0 ? line : line.slice(Math.min(common, leadingWhitespace(line)))))
+    .join("\n");
+}
+
+function checkLineLength(source, tokens, limit) {
+  // Block string bodies are prose; wrapping them would change the prompt.
+  const blockLines = new Set();
+  for (const token of tokens) {
+    if (token.kind === "blockString") {
+      for (let line = token.start.line; line <= token.end.line; line += 1) {
+        blockLines.add(line);
+      }
+    }
+  }
+
+  const diagnostics = [];
+  source.split("\n").forEach((text, line) => {
+    const length = text.endsWith("\r") ? text.length - 1 : text.length;
+    if (length <= limit || blockLines.has(line)) {
+      return;
+    }
+    diagnostics.push({
+      severity: SEVERITY.WARNING,
+      message: `Line is ${length} characters long; the limit is ${limit}.`,
+      rule: "max-line-length",
+      range: {
+        start: { line, character: limit },
+        end: { line, character: length }
+      }
+    });
+  });
+  return diagnostics;
+}
+
+function isRuleEnabled(rule, options) {
+  if (options.disable && options.disable.includes(rule)) {
+    return false;
+  }
+  if (options.enable && options.enable.includes(rule)) {
+    return true;
+  }
+  const profile = PROFILES.includes(options.profile) ? options.profile : "recommended";
+  return PROFILES.indexOf(RULE_PROFILES.get(rule) || "recommended") <= PROFILES.indexOf(profile);
+}
+
+// Lints `source` and also returns each artifact with the artifacts it
+// consumes via `using [...]`, in declaration order.
+function analyzeJulietScript(rawSource, options = {}) {
+  // Editors hide a leading byte order mark, so positions are reported as if it
+  // were absent. Callers that strip it themselves pass `byteOrderMark: true`.
+  const hasByteOrderMark = rawSource.charCodeAt(0) === 0xfeff || options.byteOrderMark === true;
+  const source = rawSource.charCodeAt(0) === 0xfeff ? rawSource.slice(1) : rawSource;
   const tokenizer = new Tokenizer(source);
   const tokenized = tokenizer.tokenize();
-  const parser = new Parser(tokenized.tokens, tokenized.diagnostics);
-  return parser.parse();
+  const parser = new Parser(tokenized.tokens, tokenized.diagnostics, options);
+  // An unterminated block string swallows the rest of the file and an
+  // unbalanced delimiter derails every statement after it; parsing past either
+  // only adds follow-on errors.
+  const truncated = tokenized.tokens.some((token) => token.unterminated);
+  const unbalanced = truncated ? null : findUnbalancedDelimiter(tokenized.tokens);
+  const diagnostics = truncated || unbalanced
+    ? [...tokenized.diagnostics, ...(unbalanced ? [unbalanced] : [])]
+    : parser.parse();
+  diagnostics.push(...checkBlockStringIndentation(tokenized.tokens));
+  if (hasByteOrderMark && isRuleEnabled("byte-order-mark", options)) {
+    const start = { line: 0, character: 0 };
+    diagnostics.push({
+      severity: SEVERITY.WARNING,
+      message: "File starts with a UTF-8 byte order mark.",
+      rule: "byte-order-mark",
+      range: { start, end: start }
+    });
+  }
+  if (isRuleEnabled("indentation", options)) {
+    diagnostics.push(...checkIndentation(source, tokenized.tokens));
+  }
+  if (isRuleEnabled("quote-style", options)) {
+    diagnostics.push(...checkQuoteStyle(tokenized.tokens));
+  }
+  if (isRuleEnabled("final-newline", options)) {
+    diagnostics.push(...checkFinalNewline(source));
+  }
+  if (isRuleEnabled("rubric-points", options)) {
+    diagnostics.push(...checkRubricPoints(parser.context.rubricTotals, options));
+  }
+  if (isRuleEnabled("search-budget", options)) {
+    const limit = options.maxSearchBudget ?? DEFAULT_MAX_SEARCH_BUDGET;
+    diagnostics.push(...checkSearchBudgets(parser.context.searchBudgets, limit));
+  }
+  if (isRuleEnabled("max-line-length", options)) {
+    const limit = options.maxLineLength ?? DEFAULT_MAX_LINE_LENGTH;
+    diagnostics.push(...checkLineLength(source, tokenized.tokens, limit));
+  }
+  diagnostics.sort((a, b) => comparePositions(a.range.start, b.range.start));
+  return {
+    diagnostics: diagnostics
+      .filter((diagnostic) => isRuleEnabled(diagnostic.rule, options))
+      .map((diagnostic) => options.profile === "strict" ? { ...diagnostic, severity: SEVERITY.ERROR } : diagnostic),
+    artifacts: parser.context.artifactDependencies,
+    sources: parser.context.sourcePaths
+  };
+}
+
+function lintJulietScript(source, options = {}) {
+  return analyzeJulietScript(source, options).diagnostics;
+}
+
+// Parses `source` without running any rules and returns its top-level
+// statements, for debugging how the linter read a script.
+function parseJulietScript(rawSource, options = {}) {
+  const source = rawSource.charCodeAt(0) === 0xfeff ? rawSource.slice(1) : rawSource;
+  const tokenized = new Tokenizer(source).tokenize();
+  const parser = new Parser(tokenized.tokens, tokenized.diagnostics, options);
+  parser.parse();
+  return { type: "script", body: parser.statements };
 }
 
 module.exports = {
+  analyzeJulietScript,
+  dedentBlockString,
   lintJulietScript,
+  parseJulietScript,
   SEVERITY
 };
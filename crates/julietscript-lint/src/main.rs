@@ -1,14 +1,43 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ffi::OsString;
 use std::fs;
-use std::io::Write;
+use std::io::{self, Read, Write};
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
 
 use anyhow::{bail, Context, Result};
-use clap::{ArgAction, Args, Parser, Subcommand};
+use clap::{ArgAction, Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
 
-const EMBEDDED_LINTER_SOURCE: &str = include_str!("linter.js");
+use cache::ResultCache;
+use clap_complete::Shell;
+use julietscript_lint::{
+    collect_files, display_path, ensure_embedded_allowed, extension_glob, filter_excluded,
+    load_files, parse_node_args, partition_oversized, run_node_bridge, run_node_linter,
+    run_node_linter_chunked, stream_node_linter, LintDiagnostic, LintFileResult, LintInputFile,
+    LintPosition, LintSummary, LinterOptions, Logger, RuleSelection, DEFAULT_SPAWN_RETRIES,
+    EMBEDDED_LINTER_SOURCE, UTF8_BOM,
+};
+
+mod cache;
+mod markdown;
+
+/// Rule IDs whose diagnostics carry a fix in the embedded linter.
+const FIXABLE_RULES: &[&str] = &[
+    "missing-semicolon",
+    "duplicate-source-path",
+    "trailing-comma",
+    "quote-style",
+    "final-newline",
+];
+/// 5 MiB: far beyond any hand-written script.
+const DEFAULT_MAX_FILE_SIZE: u64 = 5 * 1024 * 1024;
+/// Bumped whenever the JSON report changes shape incompatibly.
+const JSON_REPORT_VERSION: u64 = 1;
+/// JSON Schema for `--format json` and `--report` output.
+const REPORT_SCHEMA: &str = include_str!("report.schema.json");
 const EXAMPLE_SCRIPT: &str = r#"# JulietScript specification example
 # Reading guide:
 # - Execution is top-to-bottom.
@@ -173,59 +202,31 @@ Add an explicit criterion for migration safety and backward compatibility.
 halt "Stop after the first accepted PatchSet.";
 "#;
 
-const NODE_BRIDGE_SCRIPT: &str = r#"
-const fs = require("fs");
-
-const linterPath = process.env.JULIETSCRIPT_LINTER_PATH;
-const linterSource = process.env.JULIETSCRIPT_LINTER_SOURCE;
-
-let lintJulietScript;
-if (linterPath) {
-  try {
-    ({ lintJulietScript } = require(linterPath));
-  } catch (error) {
-    console.error(`Failed to load JulietScript linter from ${linterPath}: ${error.message}`);
-    process.exit(1);
-  }
-} else if (linterSource) {
-  try {
-    const module = { exports: {} };
-    const compile = new Function("module", "exports", "require", linterSource);
-    compile(module, module.exports, require);
-    ({ lintJulietScript } = module.exports);
-  } catch (error) {
-    console.error(`Failed to compile embedded JulietScript linter: ${error.message}`);
-    process.exit(1);
-  }
-} else {
-  console.error("No JulietScript linter source available. Set JULIETSCRIPT_LINTER_PATH or JULIETSCRIPT_LINTER_SOURCE.");
-  process.exit(1);
-}
-
-if (typeof lintJulietScript !== "function") {
-  console.error("Loaded JulietScript linter does not export lintJulietScript(source).");
-  process.exit(1);
-}
-
-let files;
-try {
-  files = JSON.parse(fs.readFileSync(0, "utf8"));
-} catch (error) {
-  console.error(`Failed to parse lint payload: ${error.message}`);
-  process.exit(1);
-}
-
-if (!Array.isArray(files)) {
-  console.error("Lint payload must be an array.");
-  process.exit(1);
-}
-
-const results = files.map((file) => ({
-  path: file.path,
-  diagnostics: lintJulietScript(file.source),
-}));
-
-process.stdout.write(JSON.stringify(results));
+const MINIMAL_TEMPLATE: &str = r#"# Minimal JulietScript skeleton.
+juliet {
+  engine = codex;
+}
+
+create FirstArtifact from juliet """
+Describe the artifact Juliet should produce.
+""";
+
+halt;
+"#;
+
+const SOURCES_TEMPLATE: &str = r#"# Seed an artifact from existing files, then build on it.
+juliet {
+  engine = codex;
+}
+
+create SourceBrief from julietArtifactSourceFiles [
+  "./docs/brief.md"
+];
+
+create BriefSummary from juliet "Summarize the source brief in one page."
+using [SourceBrief];
+
+halt;
 "#;
 
 #[derive(Parser, Debug)]
@@ -244,283 +245,2633 @@ struct Cli {
     lint: LintArgs,
 }
 
-#[derive(Subcommand, Debug, Clone, Copy)]
+#[derive(Subcommand, Debug)]
 enum CliSubcommand {
     #[command(
         about = "Print a deeply annotated JulietScript example that exercises the full linted specification."
     )]
     Example,
+
+    #[command(about = "Write a starter JulietScript template to a new file.")]
+    New(NewArgs),
+
+    #[command(
+        visible_alias = "validate-graph",
+        about = "Lint a script, then print its artifact dependency graph (DOT by default)."
+    )]
+    Graph(GraphArgs),
+
+    #[command(about = "Print a shell completion script to stdout.")]
+    Completions(CompletionsArgs),
+
+    #[command(
+        about = "Check that a JavaScript runtime is installed and the linter runs, printing PASS/FAIL per check."
+    )]
+    Doctor(DoctorArgs),
+
+    #[command(about = "Print the JSON Schema that --format json and --report output conforms to.")]
+    Schema,
 }
 
 #[derive(Args, Debug)]
-struct LintArgs {
+struct DoctorArgs {
     #[arg(
-        long = "glob",
-        required = true,
-        action = ArgAction::Append,
-        value_name = "PATTERN",
-        help = "Glob pattern for JulietScript files. Pass multiple --glob flags to lint more patterns."
+        long,
+        value_name = "FILE",
+        help = "Path to linter.js to check instead of the embedded linter. Defaults to JULIETSCRIPT_LINTER_PATH, then the embedded linter."
     )]
-    globs: Vec<String>,
+    linter: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct CompletionsArgs {
+    #[arg(
+        value_enum,
+        value_name = "SHELL",
+        help = "Shell to generate completions for."
+    )]
+    shell: Shell,
+}
+
+#[derive(Args, Debug)]
+struct GraphArgs {
+    #[arg(value_name = "FILE", help = "JulietScript file to graph.")]
+    path: PathBuf,
 
     #[arg(
         long,
-        default_value = ".",
-        value_name = "DIR",
-        help = "Base directory used to resolve relative --glob patterns."
+        value_enum,
+        default_value_t = GraphFormat::Dot,
+        help = "Print Graphviz DOT, or a JSON adjacency list mapping each artifact to its dependents."
     )]
-    root: PathBuf,
+    format: GraphFormat,
 
     #[arg(
         long,
         value_name = "FILE",
-        help = "Path to linter.js. Overrides the embedded linter implementation."
+        help = "Path to linter.js. Defaults to JULIETSCRIPT_LINTER_PATH, then the embedded linter."
     )]
     linter: Option<PathBuf>,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-enum ExitCode {
-    Clean = 0,
-    LintIssues = 1,
-}
-
-#[derive(Serialize)]
-struct LintInputFile {
-    path: String,
-    source: String,
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum GraphFormat {
+    Dot,
+    Json,
 }
 
-#[derive(Deserialize)]
-struct LintPosition {
-    line: usize,
-    character: usize,
-}
+#[derive(Args, Debug)]
+struct NewArgs {
+    #[arg(value_name = "PATH", help = "File to write the template to.")]
+    path: PathBuf,
 
-#[derive(Deserialize)]
-struct LintRange {
-    start: LintPosition,
-}
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = Template::Minimal,
+        help = "Template to write: a bare skeleton, the full annotated example, or a source-seeded pipeline."
+    )]
+    template: Template,
 
-#[derive(Deserialize)]
-struct LintDiagnostic {
-    severity: String,
-    message: String,
-    range: LintRange,
+    #[arg(long, help = "Overwrite PATH if it already exists.")]
+    force: bool,
 }
 
-#[derive(Deserialize)]
-struct LintFileResult {
-    path: String,
-    diagnostics: Vec<LintDiagnostic>,
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Template {
+    Minimal,
+    Full,
+    Sources,
 }
 
-fn main() {
-    match run() {
-        Ok(code) => std::process::exit(code as i32),
-        Err(error) => {
-            eprintln!("julietscript-lint: {error:#}");
-            std::process::exit(2);
+impl Template {
+    fn source(self) -> &'static str {
+        match self {
+            Template::Minimal => MINIMAL_TEMPLATE,
+            Template::Full => EXAMPLE_SCRIPT,
+            Template::Sources => SOURCES_TEMPLATE,
         }
     }
 }
 
-fn run() -> Result<ExitCode> {
-    let cli = Cli::parse();
+#[derive(Args, Debug)]
+struct LintArgs {
+    #[arg(
+        long = "glob",
+        action = ArgAction::Append,
+        value_name = "PATTERN",
+        help = "Glob pattern for JulietScript files. Pass multiple --glob flags to lint more patterns. Defaults to every file with an --ext extension under --root."
+    )]
+    globs: Vec<String>,
 
-    // Subcommands are handled first so that `julietscript-lint example` can run
-    // without lint flags. No Node.js process is needed for this command.
-    if matches!(cli.command, Some(CliSubcommand::Example)) {
-        print_example();
-        return Ok(ExitCode::Clean);
-    }
+    #[arg(
+        long = "ext",
+        action = ArgAction::Append,
+        value_name = "EXT",
+        default_value = "julietscript",
+        help = "File extension recognized as JulietScript, used for the default glob and --warn-unmatched. Repeat for more extensions."
+    )]
+    extensions: Vec<String>,
 
-    let root = fs::canonicalize(&cli.lint.root).with_context(|| {
-        format!(
-            "failed to resolve --root directory '{}'",
-            cli.lint.root.display()
-        )
-    })?;
+    #[arg(
+        long,
+        default_value = ".",
+        value_name = "DIR",
+        help = "Base directory used to resolve relative --glob patterns."
+    )]
+    root: PathBuf,
 
-    let files = collect_files(&root, &cli.lint.globs)?;
-    if files.is_empty() {
-        bail!(
-            "no files matched. Provided patterns: {}",
-            cli.lint
-                .globs
-                .iter()
-                .map(String::as_str)
-                .collect::<Vec<_>>()
-                .join(", ")
-        );
-    }
+    #[arg(
+        long = "exclude",
+        action = ArgAction::Append,
+        value_name = "PATTERN",
+        help = "Skip files whose path relative to --root matches this glob. Repeatable."
+    )]
+    excludes: Vec<String>,
 
-    let lint_inputs = load_files(&files)?;
-    let linter_path = resolve_linter_path(cli.lint.linter)?;
-    let mut lint_results = run_node_linter(linter_path.as_deref(), &lint_inputs)?;
-    lint_results.sort_by(|a, b| a.path.cmp(&b.path));
+    #[arg(
+        long = "relative-to",
+        value_name = "DIR",
+        conflicts_with = "stdin",
+        help = "Print file paths in diagnostics relative to DIR. Defaults to --root; set it when globbing and display should use different directories."
+    )]
+    relative_to: Option<PathBuf>,
 
-    let mut issue_count = 0usize;
-    let mut error_count = 0usize;
-    let mut warning_count = 0usize;
+    #[arg(
+        long = "no-canonicalize",
+        conflicts_with = "stdin",
+        help = "Keep file paths as matched instead of resolving symlinks, so scripts in symlinked directories are reported under the symlinked path. A file reachable through two paths may then be linted twice."
+    )]
+    no_canonicalize: bool,
 
-    for file in &lint_results {
-        for diagnostic in &file.diagnostics {
-            issue_count += 1;
-            match diagnostic.severity.as_str() {
-                "error" => error_count += 1,
-                "warning" => warning_count += 1,
-                _ => {}
-            }
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Read settings from a JSON config file. Flags given on the command line take precedence."
+    )]
+    config: Option<PathBuf>,
 
-            println!(
-                "{}:{}:{}: {}: {}",
-                file.path,
-                diagnostic.range.start.line + 1,
-                diagnostic.range.start.character + 1,
-                diagnostic.severity,
-                diagnostic.message
-            );
-        }
-    }
+    #[arg(
+        long = "print-config",
+        help = "Print the effective configuration (config file merged with flags) as JSON and exit without linting."
+    )]
+    print_config: bool,
 
-    println!(
-        "Linted {} file(s): {} issue(s) ({} error(s), {} warning(s)).",
-        lint_results.len(),
-        issue_count,
-        error_count,
-        warning_count
-    );
+    #[arg(
+        long = "input-list",
+        value_name = "FILE",
+        conflicts_with = "globs",
+        help = "Lint exactly the files listed in FILE, one path per line (relative to --root or absolute), instead of globbing."
+    )]
+    input_list: Option<PathBuf>,
 
-    if issue_count > 0 {
-        Ok(ExitCode::LintIssues)
-    } else {
-        Ok(ExitCode::Clean)
-    }
-}
+    #[arg(
+        long,
+        conflicts_with_all = ["globs", "input_list", "warn_unmatched", "fix_dry_run"],
+        help = "Lint source read from stdin instead of files on disk. With --fix, write the fixed source to stdout and the remaining diagnostics to stderr."
+    )]
+    stdin: bool,
 
-fn print_example() {
-    print!("{EXAMPLE_SCRIPT}");
-}
+    #[arg(
+        long = "stdin-format",
+        value_enum,
+        default_value_t = StdinFormat::Raw,
+        requires = "stdin",
+        help = "How to read --stdin: 'raw' is one script; 'ndjson' is one {\"path\": ..., \"source\": ...} object per line, each linted under its own path."
+    )]
+    stdin_format: StdinFormat,
 
-fn collect_files(root: &Path, patterns: &[String]) -> Result<Vec<PathBuf>> {
-    let mut files = BTreeSet::new();
+    #[arg(
+        long = "stdin-filename",
+        value_name = "PATH",
+        requires = "stdin",
+        help = "Path to report for raw --stdin source. Defaults to '<stdin>'."
+    )]
+    stdin_filename: Option<String>,
 
-    for pattern in patterns {
-        let resolved_pattern = if Path::new(pattern).is_absolute() {
-            pattern.clone()
-        } else {
-            root.join(pattern).to_string_lossy().into_owned()
-        };
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Write the formatted report (diagnostics and summary) to FILE instead of stdout. Parent directories are created as needed."
+    )]
+    output: Option<PathBuf>,
 
-        let entries = glob::glob(&resolved_pattern)
-            .with_context(|| format!("invalid glob pattern '{}'", pattern))?;
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = LineEnding::Lf,
+        help = "Line endings for the text and compact report. JSON output always uses LF."
+    )]
+    eol: LineEnding,
 
-        for entry in entries {
-            let path = entry
-                .with_context(|| format!("error while expanding glob pattern '{}'", pattern))?;
-            if path.is_file() {
-                files
-                    .insert(fs::canonicalize(path).context("failed to canonicalize matched path")?);
-            }
-        }
-    }
+    #[arg(
+        long,
+        conflicts_with = "stdin",
+        help = "Print the number of files matched after --glob and --exclude, then exit without linting."
+    )]
+    count: bool,
 
-    Ok(files.into_iter().collect())
-}
+    #[arg(
+        long = "allow-empty",
+        conflicts_with = "stdin",
+        help = "Treat matching no files as success instead of an error."
+    )]
+    allow_empty: bool,
 
-fn load_files(paths: &[PathBuf]) -> Result<Vec<LintInputFile>> {
-    let mut files = Vec::with_capacity(paths.len());
-    for path in paths {
-        let source = fs::read_to_string(path)
-            .with_context(|| format!("failed to read '{}'", path.display()))?;
-        files.push(LintInputFile {
-            path: path.display().to_string(),
-            source,
-        });
-    }
-    Ok(files)
-}
+    #[arg(
+        long = "check-sources",
+        conflicts_with = "stdin",
+        help = "Report julietArtifactSourceFiles paths that do not exist, resolved against each script's own directory."
+    )]
+    check_sources: bool,
 
-fn resolve_linter_path(linter_arg: Option<PathBuf>) -> Result<Option<PathBuf>> {
-    if let Some(path) = linter_arg {
-        if !path.is_file() {
-            bail!("--linter path '{}' is not a file", path.display());
-        }
-        return fs::canonicalize(path)
-            .context("failed to canonicalize --linter path")
-            .map(Some);
-    }
+    #[arg(
+        long,
+        conflicts_with_all = ["stdin", "stream", "fail_fast", "check_sources"],
+        help = "Reuse diagnostics for files unchanged since the last run, stored in .julietlint-cache.json. The cache resets when the linter or rule options change."
+    )]
+    cache: bool,
 
-    if let Some(env_path) = std::env::var_os("JULIETSCRIPT_LINTER_PATH") {
-        let path = PathBuf::from(env_path);
-        if !path.is_file() {
-            bail!(
-                "JULIETSCRIPT_LINTER_PATH '{}' is not a file",
-                path.display()
-            );
-        }
-        return fs::canonicalize(path)
-            .context("failed to canonicalize JULIETSCRIPT_LINTER_PATH")
-            .map(Some);
-    }
+    #[arg(
+        long = "cache-dir",
+        value_name = "DIR",
+        requires = "cache",
+        help = "Directory for the --cache file instead of --root, such as a CI cache mount. Created if missing; if it cannot be written, the run warns and continues uncached."
+    )]
+    cache_dir: Option<PathBuf>,
 
-    Ok(None)
-}
+    #[arg(
+        long = "max-file-size",
+        value_name = "BYTES",
+        default_value_t = DEFAULT_MAX_FILE_SIZE,
+        help = "Skip files larger than BYTES with a notice instead of linting them. 0 disables the limit."
+    )]
+    max_file_size: u64,
 
-fn run_node_linter(
-    linter_path: Option<&Path>,
-    files: &[LintInputFile],
-) -> Result<Vec<LintFileResult>> {
-    let payload = serde_json::to_vec(files).context("failed to serialize lint payload")?;
-
-    let mut command = Command::new("node");
-    command
-        .arg("-e")
-        .arg(NODE_BRIDGE_SCRIPT)
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
-
-    if let Some(path) = linter_path {
-        command.env("JULIETSCRIPT_LINTER_PATH", path);
-    } else if !EMBEDDED_LINTER_SOURCE.trim().is_empty() {
-        command.env("JULIETSCRIPT_LINTER_SOURCE", EMBEDDED_LINTER_SOURCE);
-    } else {
-        bail!("no linter source available. Provide --linter FILE or set JULIETSCRIPT_LINTER_PATH");
-    }
+    #[arg(
+        long = "warn-unmatched",
+        help = "After the summary, list files with an --ext extension under --root that no --glob matched."
+    )]
+    warn_unmatched: bool,
 
-    let mut child = command
-        .spawn()
-        .context("failed to execute 'node'. Install Node.js (18+) to run julietscript-lint")?;
+    #[arg(
+        long,
+        value_name = "FILE",
+        action = ArgAction::Append,
+        help = "Path to linter.js. Overrides the embedded linter implementation. Repeat to run several linters over the same files and merge their diagnostics."
+    )]
+    linter: Vec<PathBuf>,
 
-    {
-        let mut stdin = child
-            .stdin
-            .take()
-            .context("failed to open stdin for node bridge process")?;
-        stdin
-            .write_all(&payload)
-            .context("failed to send lint payload to node bridge")?;
-    }
-
-    let output = child
-        .wait_with_output()
-        .context("failed while waiting for node bridge process")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let message = stderr.trim();
-        if message.is_empty() {
-            bail!("node bridge exited with status {}", output.status);
-        } else {
-            bail!(
-                "node bridge exited with status {}: {}",
-                output.status,
-                message
-            );
-        }
-    }
+    #[arg(
+        short,
+        long,
+        action = ArgAction::Count,
+        help = "Log glob expansion, matched files, and Node bridge details to stderr. Repeat (-vv) for more detail."
+    )]
+    verbose: u8,
+
+    #[arg(
+        long = "print-linter",
+        help = "Print the source of the linter that would be used, then exit."
+    )]
+    print_linter: bool,
+
+    #[arg(
+        long = "print-ast",
+        help = "Print each file's parse tree as JSON, as the linter read it, then exit without running rules."
+    )]
+    print_ast: bool,
+
+    #[arg(
+        long = "no-default-linter",
+        help = "Fail instead of falling back to the embedded linter when neither --linter nor JULIETSCRIPT_LINTER_PATH is set."
+    )]
+    no_default_linter: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = OutputFormat::Text,
+        help = "Output format for diagnostics."
+    )]
+    format: OutputFormat,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = PositionBase::One,
+        help = "Whether line and character numbers in JSON output (--format json and --report) count from 0 or from 1. Defaults to 1, like the text output."
+    )]
+    base: PositionBase,
+
+    #[arg(
+        long = "json-legacy",
+        help = "Write JSON output (--format json and --report) as the bare array of file results used before version 1 of the report format. Deprecated."
+    )]
+    json_legacy: bool,
+
+    #[arg(
+        long = "only-failing",
+        help = "Leave files without diagnostics out of --format json output. By default every linted file gets an entry, with an empty diagnostics array when clean."
+    )]
+    only_failing: bool,
 
-    serde_json::from_slice(&output.stdout).context("failed to decode JSON results from node bridge")
+    #[arg(
+        long,
+        help = "After the summary, print issue counts per rule ID, most frequent first."
+    )]
+    stats: bool,
+
+    #[arg(
+        long = "highlight-worst",
+        help = "After the summary, name the file with the most errors (then most issues); ties go to the first path."
+    )]
+    highlight_worst: bool,
+
+    #[arg(
+        long = "group-by-rule",
+        conflicts_with_all = ["stream", "max_issues_per_file"],
+        help = "Organize text output into one section per rule ID, followed by per-rule counts and the summary."
+    )]
+    group_by_rule: bool,
+
+    #[arg(
+        long = "no-summary",
+        help = "Omit the trailing 'Linted N file(s)' line from text and compact output. Diagnostics and the exit code are unchanged."
+    )]
+    no_summary: bool,
+
+    #[arg(
+        long = "quiet-clean",
+        help = "Print nothing when no issues are found, except warnings about files that were not linted; reports with issues print as usual."
+    )]
+    quiet_clean: bool,
+
+    #[arg(
+        long,
+        help = "After the run, print wall-clock time spent collecting files, reading them, and in the Node bridge to stderr."
+    )]
+    timings: bool,
+
+    #[arg(
+        long = "max-issues-per-file",
+        value_name = "N",
+        help = "Print at most N diagnostics per file in text output. The summary still counts every issue."
+    )]
+    max_issues_per_file: Option<usize>,
+
+    #[arg(
+        long = "fixable-only",
+        conflicts_with = "stream",
+        help = "Print only diagnostics that have an autofix, with a note of how many others were hidden. The summary and exit code still count every issue."
+    )]
+    fixable_only: bool,
+
+    #[arg(
+        long = "only-errors",
+        conflicts_with_all = ["stream", "only_warnings"],
+        help = "Print only error diagnostics, with a note of how many warnings were hidden. The summary and exit code still count every issue."
+    )]
+    only_errors: bool,
+
+    #[arg(
+        long = "only-warnings",
+        conflicts_with = "stream",
+        help = "Print only warning diagnostics, with a note of how many errors were hidden. The summary and exit code still count every issue."
+    )]
+    only_warnings: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["fix", "fix_dry_run", "fix_only"],
+        help = "Print each file's diagnostics as soon as it is linted, in completion order. The summary still prints last. Not supported with --format json."
+    )]
+    stream: bool,
+
+    #[arg(
+        long = "fail-fast",
+        conflicts_with_all = ["fix", "fix_dry_run", "fix_only"],
+        help = "Stop linting after the first file with an error and report only the files linted so far."
+    )]
+    fail_fast: bool,
+
+    #[arg(
+        long,
+        short = 'j',
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u64).range(1..),
+        conflicts_with_all = ["stream", "fail_fast"],
+        help = "Split the files into N chunks, each linted by its own Node process."
+    )]
+    jobs: u64,
+
+    #[arg(
+        long = "max-inflight",
+        value_name = "N",
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Run at most N Node processes at once; remaining --jobs chunks wait for a free slot. Defaults to --jobs."
+    )]
+    max_inflight: Option<u64>,
+
+    #[arg(
+        long = "spawn-retries",
+        value_name = "N",
+        default_value_t = DEFAULT_SPAWN_RETRIES,
+        help = "Retry starting node up to N times, with exponential backoff, when the system is temporarily out of processes or memory."
+    )]
+    spawn_retries: u32,
+
+    #[arg(
+        long = "bridge-node-args",
+        value_name = "FLAGS",
+        allow_hyphen_values = true,
+        help = "Extra node flags for the lint bridge, such as \"--no-warnings --max-old-space-size=4096\". Only runtime-tuning flags (--no-warnings, --max-old-space-size=N, --stack-size=N, ...) are accepted."
+    )]
+    bridge_node_args: Option<String>,
+
+    #[arg(
+        long = "dump-payload",
+        value_name = "FILE",
+        help = "Write the JSON payload sent to the lint bridge to FILE (\"-\" for stderr), to replay bridge issues by hand."
+    )]
+    dump_payload: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Buffer results and print them sorted by path. Overrides --stream."
+    )]
+    sort: bool,
+
+    #[arg(
+        long = "fail-level",
+        value_enum,
+        help = "Lowest severity that makes the run exit 1. The default 'error' lets warning-only runs pass; use 'warning' to fail on any issue."
+    )]
+    fail_level: Option<FailLevel>,
+
+    #[arg(
+        long = "max-issues",
+        value_name = "N",
+        help = "Exit 1 only when the total issue count, errors and warnings alike, is above N. Replaces the --fail-level check, so a run within the budget exits 0."
+    )]
+    max_issues: Option<usize>,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "Rule preset: 'minimal' runs only syntax checks, 'recommended' is the default rule set, and 'strict' runs every rule and reports all issues as errors. --enable and --disable apply on top."
+    )]
+    profile: Option<Profile>,
+
+    #[arg(
+        long = "enable",
+        value_name = "RULE",
+        action = ArgAction::Append,
+        help = "Turn on an opt-in rule by ID (for example 'indentation'). Repeatable."
+    )]
+    enable: Vec<String>,
+
+    #[arg(
+        long = "disable",
+        value_name = "RULE",
+        action = ArgAction::Append,
+        help = "Suppress diagnostics for a rule ID. Repeatable."
+    )]
+    disable: Vec<String>,
+
+    #[arg(
+        long = "error-on",
+        value_name = "RULE",
+        action = ArgAction::Append,
+        help = "Report a rule's diagnostics as errors, so that rule alone fails the run. Repeatable."
+    )]
+    error_on: Vec<String>,
+
+    #[arg(
+        long = "fail-on",
+        value_name = "RULE",
+        action = ArgAction::Append,
+        help = "Exit 1 whenever this rule reports anything, whatever its severity and --fail-level. Repeatable."
+    )]
+    fail_on: Vec<String>,
+
+    #[arg(
+        long = "exit-zero",
+        help = "Exit 0 whenever linting completes, even with issues, so a later step can decide pass or fail. Tool and argument errors still exit 2."
+    )]
+    exit_zero: bool,
+
+    #[arg(
+        long = "max-line-length",
+        value_name = "N",
+        help = "Enable the max-line-length rule with a limit of N characters. Lines inside triple-quoted strings are not checked."
+    )]
+    max_line_length: Option<NonZeroUsize>,
+
+    #[arg(
+        long = "require-halt",
+        help = "Enable the require-halt rule, which warns when a script has no top-level 'halt' statement."
+    )]
+    require_halt: bool,
+
+    #[arg(
+        long = "strict-eof",
+        help = "Enable the final-newline rule, which warns when a file does not end with exactly one newline. --fix repairs it."
+    )]
+    strict_eof: bool,
+
+    #[arg(
+        long = "max-rubric-points",
+        value_name = "N",
+        help = "Enable the rubric-points rule: warn when a rubric's criterion points add up to more than N."
+    )]
+    max_rubric_points: Option<u64>,
+
+    #[arg(
+        long = "exact-rubric-points",
+        value_name = "N",
+        help = "Enable the rubric-points rule: report an error when a rubric's criterion points do not add up to exactly N. Takes precedence over --max-rubric-points."
+    )]
+    exact_rubric_points: Option<u64>,
+
+    #[arg(
+        long = "max-search-budget",
+        value_name = "N",
+        help = "Enable the search-budget rule: warn when a cadence's variants, sprints, and keep best imply more than N candidates."
+    )]
+    max_search_budget: Option<NonZeroU64>,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Also write the full JSON results to FILE, independent of --format."
+    )]
+    report: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with = "stream",
+        help = "Suppress diagnostics already recorded in FILE, a previous --report output. Matches on path, rule, and message, so line shifts do not matter."
+    )]
+    baseline: Option<PathBuf>,
+
+    #[arg(
+        long = "baseline-diff",
+        requires = "baseline",
+        help = "Print 'new=N resolved=M', then only the new diagnostics and the resolved baseline entries. Exits 1 only if there are new diagnostics."
+    )]
+    baseline_diff: bool,
+
+    #[arg(
+        long,
+        help = "Apply available autofixes to matched files, then report the remaining issues."
+    )]
+    fix: bool,
+
+    #[arg(
+        long = "fix-dry-run",
+        conflicts_with = "fix",
+        help = "Print a unified diff of available autofixes without writing files. Exits 1 if any fixes are available."
+    )]
+    fix_dry_run: bool,
+
+    #[arg(
+        long = "fix-only",
+        value_name = "RULE",
+        action = ArgAction::Append,
+        help = "Only apply autofixes for this rule ID; other fixable issues are still reported. Repeatable. Implies --fix unless --fix-dry-run is given."
+    )]
+    fix_only: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum FailLevel {
+    /// Exit 1 only when an error is reported.
+    #[default]
+    Error,
+    /// Exit 1 when any error or warning is reported.
+    Warning,
+    /// Always exit 0 once linting completes.
+    Never,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum StdinFormat {
+    /// The whole of stdin is one script.
+    Raw,
+    /// One JSON object with `path` and `source` per line.
+    Ndjson,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum LineEnding {
+    /// `\n`, on every platform.
+    Lf,
+    /// `\r\n`, for Windows tools and golden files.
+    Crlf,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum PositionBase {
+    /// Lines and characters count from 0, as editors' APIs do.
+    #[value(name = "0")]
+    Zero,
+    /// Lines and characters count from 1, matching the text output.
+    #[value(name = "1")]
+    One,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Profile {
+    /// Only syntax errors.
+    Minimal,
+    /// Syntax errors plus the default semantic warnings.
+    Recommended,
+    /// Every rule, including opt-in ones, reported as errors.
+    Strict,
+}
+
+/// Settings that can come from `--config FILE`. A flag given on the command
+/// line replaces the matching field; list flags do not append to the file's
+/// lists.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct Config {
+    globs: Vec<String>,
+    excludes: Vec<String>,
+    enable: Vec<String>,
+    disable: Vec<String>,
+    error_on: Vec<String>,
+    fail_on: Vec<String>,
+    max_line_length: Option<NonZeroUsize>,
+    require_halt: bool,
+    strict_eof: bool,
+    max_rubric_points: Option<u64>,
+    exact_rubric_points: Option<u64>,
+    max_search_budget: Option<NonZeroU64>,
+    profile: Option<Profile>,
+    fail_level: FailLevel,
+    /// Relative paths in a config file resolve against the file's directory.
+    linter: Option<PathBuf>,
+}
+
+impl Config {
+    fn load(path: Option<&Path>) -> Result<Self> {
+        let Some(path) = path else {
+            return Ok(Config::default());
+        };
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read --config file '{}'", path.display()))?;
+        let mut config: Config = serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse --config file '{}'", path.display()))?;
+        if let (Some(linter), Some(dir)) = (&config.linter, path.parent()) {
+            config.linter = Some(dir.join(linter));
+        }
+        Ok(config)
+    }
+
+    fn merge(mut self, args: &LintArgs) -> Self {
+        fn replace(field: &mut Vec<String>, flags: &[String]) {
+            if !flags.is_empty() {
+                *field = flags.to_vec();
+            }
+        }
+        replace(&mut self.globs, &args.globs);
+        replace(&mut self.excludes, &args.excludes);
+        replace(&mut self.enable, &args.enable);
+        replace(&mut self.disable, &args.disable);
+        replace(&mut self.error_on, &args.error_on);
+        replace(&mut self.fail_on, &args.fail_on);
+        self.max_line_length = args.max_line_length.or(self.max_line_length);
+        self.require_halt |= args.require_halt;
+        self.strict_eof |= args.strict_eof;
+        self.max_rubric_points = args.max_rubric_points.or(self.max_rubric_points);
+        self.exact_rubric_points = args.exact_rubric_points.or(self.exact_rubric_points);
+        self.max_search_budget = args.max_search_budget.or(self.max_search_budget);
+        self.profile = args.profile.or(self.profile);
+        self.fail_level = args.fail_level.unwrap_or(self.fail_level);
+        self.linter = args.linter.first().cloned().or(self.linter);
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum OutputFormat {
+    /// One `path:line:col: severity: message` line per diagnostic, then a summary.
+    Text,
+    /// One line per file with issues, showing its count and first message, then a summary.
+    Compact,
+    /// A versioned JSON object with per-file results, full diagnostic ranges, and a summary.
+    Json,
+    /// A Code Climate issue array with root-relative paths, for GitLab code quality reports.
+    Codeclimate,
+}
+
+impl OutputFormat {
+    /// Formats whose stdout must stay a single JSON document.
+    fn is_json(self) -> bool {
+        matches!(self, OutputFormat::Json | OutputFormat::Codeclimate)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ExitCode {
+    Clean = 0,
+    LintIssues = 1,
+}
+
+fn main() {
+    match run() {
+        Ok(code) => std::process::exit(code as i32),
+        Err(error) => {
+            eprintln!("julietscript-lint: {error:#}");
+            std::process::exit(2);
+        }
+    }
+}
+
+fn run() -> Result<ExitCode> {
+    let cli = parse_cli()?;
+    // Errors still exit 2; only the lint verdict is overridden.
+    let exit_zero = cli.lint.exit_zero;
+    let code = run_cli(cli)?;
+    Ok(if exit_zero { ExitCode::Clean } else { code })
+}
+
+fn run_cli(cli: Cli) -> Result<ExitCode> {
+    // Subcommands are handled first so that `julietscript-lint example` and
+    // `julietscript-lint new` can run without lint flags. No Node.js process is
+    // needed for these commands.
+    match cli.command {
+        Some(CliSubcommand::Example) => {
+            print_example();
+            return Ok(ExitCode::Clean);
+        }
+        Some(CliSubcommand::Schema) => {
+            print!("{REPORT_SCHEMA}");
+            return Ok(ExitCode::Clean);
+        }
+        Some(CliSubcommand::New(args)) => {
+            write_template(&args)?;
+            return Ok(ExitCode::Clean);
+        }
+        Some(CliSubcommand::Graph(args)) => return print_graph(args),
+        Some(CliSubcommand::Doctor(args)) => return run_doctor(args),
+        Some(CliSubcommand::Completions(args)) => {
+            let mut command = Cli::command();
+            let bin = command.get_name().to_owned();
+            clap_complete::generate(args.shell, &mut command, bin, &mut io::stdout().lock());
+            return Ok(ExitCode::Clean);
+        }
+        None => {}
+    }
+
+    if cli.lint.print_linter {
+        let linter_path = resolve_linter_path(cli.lint.linter.into_iter().next())?;
+        print_linter(linter_path.as_deref(), !cli.lint.no_default_linter)?;
+        return Ok(ExitCode::Clean);
+    }
+
+    let config = Config::load(cli.lint.config.as_deref())?.merge(&cli.lint);
+    if cli.lint.print_config {
+        let rendered =
+            serde_json::to_string_pretty(&config).context("failed to serialize configuration")?;
+        println!("{rendered}");
+        return Ok(ExitCode::Clean);
+    }
+
+    for rule in &cli.lint.fix_only {
+        if !FIXABLE_RULES.contains(&rule.as_str()) {
+            bail!(
+                "rule '{rule}' has no autofix. Fixable rules: {}",
+                FIXABLE_RULES.join(", ")
+            );
+        }
+    }
+
+    let mut timings = Timings::new(cli.lint.timings);
+    let canonicalize = !cli.lint.no_canonicalize;
+    let root = resolve_dir(&cli.lint.root, canonicalize).with_context(|| {
+        format!(
+            "failed to resolve --root directory '{}'",
+            cli.lint.root.display()
+        )
+    })?;
+
+    let extension_pattern = extension_glob(&cli.lint.extensions);
+    let globs = if config.globs.is_empty() {
+        vec![extension_pattern.clone()]
+    } else {
+        config.globs.clone()
+    };
+
+    let log = Logger {
+        verbosity: cli.lint.verbose,
+    };
+    let (files, mut lint_inputs, oversized) = if cli.lint.stdin {
+        let started = Instant::now();
+        let inputs = read_stdin_inputs(cli.lint.stdin_format, cli.lint.stdin_filename.as_deref())?;
+        timings.read += started.elapsed();
+        (Vec::new(), inputs, Vec::new())
+    } else {
+        let started = Instant::now();
+        let files = match &cli.lint.input_list {
+            Some(list) => read_input_list(&root, list, canonicalize)?,
+            None => collect_files(&root, &globs, log, canonicalize)?,
+        };
+        let files = filter_excluded(&root, files, &config.excludes)?;
+        timings.collect += started.elapsed();
+        if files.is_empty() && cli.lint.input_list.is_some() && !cli.lint.allow_empty {
+            bail!("--input-list lists no files");
+        }
+        if files.is_empty() && !cli.lint.allow_empty {
+            bail!(
+                "no files matched. Provided patterns: {}",
+                globs
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        if cli.lint.count {
+            println!("{}", files.len());
+            return Ok(ExitCode::Clean);
+        }
+
+        let started = Instant::now();
+        let (small, oversized) = match cli.lint.max_file_size {
+            0 => (files.clone(), Vec::new()),
+            limit => partition_oversized(&files, limit)?,
+        };
+        let lint_inputs = load_files(&small)?;
+        timings.read += started.elapsed();
+        (files, lint_inputs, oversized)
+    };
+    // Paths render relative to --relative-to, else to --root. Standard input
+    // keeps the name it was given.
+    let relative_to = match &cli.lint.relative_to {
+        Some(dir) => Some(resolve_dir(dir, canonicalize).with_context(|| {
+            format!(
+                "failed to resolve --relative-to directory '{}'",
+                dir.display()
+            )
+        })?),
+        None if cli.lint.stdin => None,
+        None => Some(root.clone()),
+    };
+    if let Some(base) = &relative_to {
+        for input in &mut lint_inputs {
+            input.path = display_path(&relative_path(&input.file, base));
+        }
+    }
+    // Setting a rule's limit, or its shortcut flag, turns the rule on.
+    let mut enable = config.enable;
+    if config.max_line_length.is_some() {
+        enable.push("max-line-length".to_owned());
+    }
+    if config.require_halt {
+        enable.push("require-halt".to_owned());
+    }
+    if config.strict_eof {
+        enable.push("final-newline".to_owned());
+    }
+    if config.max_rubric_points.is_some() || config.exact_rubric_points.is_some() {
+        enable.push("rubric-points".to_owned());
+    }
+    if config.max_search_budget.is_some() {
+        enable.push("search-budget".to_owned());
+    }
+    let linter = LinterOptions {
+        path: resolve_linter_path(config.linter)?,
+        allow_embedded: !cli.lint.no_default_linter,
+        rules: RuleSelection {
+            enable,
+            disable: config.disable,
+            max_line_length: config.max_line_length.map(NonZeroUsize::get),
+            max_rubric_points: config.max_rubric_points,
+            exact_rubric_points: config.exact_rubric_points,
+            max_search_budget: config.max_search_budget.map(NonZeroU64::get),
+            profile: config
+                .profile
+                .and_then(|profile| profile.to_possible_value())
+                .map(|value| value.get_name().to_owned()),
+            external_artifacts: cli.lint.stdin,
+            check_sources: cli.lint.check_sources,
+        },
+        log,
+        spawn_retries: cli.lint.spawn_retries,
+        node_args: match &cli.lint.bridge_node_args {
+            Some(text) => parse_node_args(text)?,
+            None => Vec::new(),
+        },
+    };
+    // Further --linter flags run alongside the first with the same options.
+    let extra_linters = cli
+        .lint
+        .linter
+        .iter()
+        .skip(1)
+        .map(|path| {
+            Ok(LinterOptions {
+                path: resolve_linter_path(Some(path.clone()))?,
+                ..linter.clone()
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if let Some(path) = &cli.lint.dump_payload {
+        dump_payload(path, &markdown::split_blocks(&lint_inputs))?;
+    }
+    if cli.lint.print_ast {
+        return print_ast(&linter, &markdown::split_blocks(&lint_inputs));
+    }
+    let jobs = cli.lint.jobs as usize;
+    let max_inflight = cli.lint.max_inflight.map_or(jobs, |limit| limit as usize);
+    let mut out = open_report_output(cli.lint.output.as_deref())?;
+    if cli.lint.eol == LineEnding::Crlf && !cli.lint.format.is_json() {
+        out = Box::new(CrlfWriter(out));
+    }
+    let streaming = cli.lint.stream && !cli.lint.sort;
+    if cli.lint.group_by_rule && cli.lint.format != OutputFormat::Text {
+        bail!("--group-by-rule only applies to --format text");
+    }
+    if cli.lint.only_failing && cli.lint.format != OutputFormat::Json {
+        bail!("--only-failing only applies to --format json");
+    }
+    if streaming && cli.lint.format.is_json() {
+        bail!("--stream is not supported with JSON output formats");
+    }
+    if !extra_linters.is_empty() && (streaming || cli.lint.fail_fast || cli.lint.cache) {
+        bail!("multiple --linter flags are not supported with --stream, --fail-fast, or --cache");
+    }
+    if (streaming || cli.lint.fail_fast || cli.lint.cache)
+        && lint_inputs
+            .iter()
+            .any(|input| markdown::is_markdown(&input.file))
+    {
+        bail!("Markdown files are not supported with --stream, --fail-fast, or --cache");
+    }
+    let cache = if cli.lint.cache {
+        let dir = cli.lint.cache_dir.as_deref().unwrap_or(&root);
+        Some(ResultCache::load(dir, cache_key(&linter)?))
+    } else {
+        None
+    };
+    let started = Instant::now();
+    let mut lint_results = if streaming || cli.lint.fail_fast {
+        stream_node_linter(&linter, &lint_inputs, |file| {
+            check_sources(file, &lint_inputs);
+            normalize_messages(file);
+            elevate_rules(file, &config.error_on);
+            if streaming {
+                match cli.lint.format {
+                    OutputFormat::Compact => print_compact_file(&mut out, file),
+                    _ => print_text_file(&mut out, file, cli.lint.max_issues_per_file),
+                }
+                .context("failed to write report")?;
+            }
+            let has_error = file
+                .diagnostics
+                .iter()
+                .any(|diagnostic| diagnostic.severity == "error");
+            Ok(if cli.lint.fail_fast && has_error {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            })
+        })?
+    } else if let Some(cache) = &cache {
+        cache.lint(
+            &linter,
+            &lint_inputs,
+            jobs,
+            max_inflight,
+            &mut timings.workers,
+        )?
+    } else {
+        run_linters(
+            &linter,
+            &extra_linters,
+            &lint_inputs,
+            jobs,
+            max_inflight,
+            &mut timings.workers,
+        )?
+    };
+    timings.bridge += started.elapsed();
+    lint_results.sort_by(|a, b| a.path.cmp(&b.path));
+
+    if cli.lint.fix_dry_run {
+        return Ok(print_fix_previews(
+            &lint_inputs,
+            &lint_results,
+            &cli.lint.fix_only,
+        ));
+    }
+
+    let fix = cli.lint.fix || !cli.lint.fix_only.is_empty();
+    if fix && cli.lint.stdin {
+        if cli.lint.stdin_format != StdinFormat::Raw {
+            bail!("--fix with --stdin needs --stdin-format raw");
+        }
+        return fix_stdin(
+            &linter,
+            &mut lint_inputs,
+            lint_results,
+            &cli.lint.fix_only,
+            &config.error_on,
+        );
+    }
+    if fix && write_fixes(&mut lint_inputs, &lint_results, &cli.lint.fix_only)? > 0 {
+        // Report what is left after fixing rather than what was fixed.
+        let started = Instant::now();
+        lint_results = run_linters(
+            &linter,
+            &extra_linters,
+            &lint_inputs,
+            jobs,
+            max_inflight,
+            &mut timings.workers,
+        )?;
+        timings.bridge += started.elapsed();
+        lint_results.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+
+    if let Some(cache) = cache {
+        cache.save(&lint_results, &lint_inputs);
+    }
+    for file in &mut lint_results {
+        check_sources(file, &lint_inputs);
+        normalize_messages(file);
+        elevate_rules(file, &config.error_on);
+    }
+    annotate_offsets(&mut lint_results, &lint_inputs);
+    let json_style = JsonStyle {
+        base: cli.lint.base,
+        legacy: cli.lint.json_legacy,
+    };
+    if let Some(report_path) = &cli.lint.report {
+        let summary = LintSummary::from_results(&lint_results);
+        write_json_report(report_path, &lint_results, &summary, json_style)?;
+    }
+
+    if let Some(baseline_path) = &cli.lint.baseline {
+        let baseline = read_baseline(baseline_path)?;
+        let resolved = subtract_baseline(&mut lint_results, &baseline);
+        if cli.lint.baseline_diff {
+            return print_baseline_diff(&mut out, &lint_results, &resolved)
+                .context("failed to write report");
+        }
+    }
+
+    let summary = LintSummary::from_results(&lint_results);
+    let fail_on_hit = lint_results
+        .iter()
+        .flat_map(|file| &file.diagnostics)
+        .filter_map(|diagnostic| diagnostic.rule.as_ref())
+        .any(|rule| config.fail_on.contains(rule));
+    // Files that were never linted are reported even by --quiet-clean: they
+    // are the only sign that a clean run did not cover everything.
+    let mut skipped = Vec::new();
+    for file in &oversized {
+        skipped.push(format!(
+            "warning: {} is {} bytes, over the --max-file-size limit of {}, and was not linted.",
+            render_path(&file.path, relative_to.as_deref()),
+            file.size,
+            cli.lint.max_file_size
+        ));
+    }
+    if cli.lint.warn_unmatched {
+        let unmatched = find_unmatched_files(&root, &extension_pattern, &files, log, canonicalize)?;
+        for path in &unmatched {
+            skipped.push(format!(
+                "warning: {} was not matched by any --glob pattern and was not linted.",
+                render_path(path, relative_to.as_deref())
+            ));
+        }
+    }
+    if cli.lint.quiet_clean && summary.issues == 0 {
+        for line in &skipped {
+            print_notice(&mut out, cli.lint.format, line).context("failed to write report")?;
+        }
+        out.flush().context("failed to write report")?;
+        return Ok(ExitCode::Clean);
+    }
+    let mut hidden = 0;
+    if cli.lint.fixable_only {
+        for file in &mut lint_results {
+            let before = file.diagnostics.len();
+            file.diagnostics
+                .retain(|diagnostic| diagnostic.fix.is_some());
+            hidden += before - file.diagnostics.len();
+        }
+    }
+    let (shown_severity, hidden_severity, severity_flag) = if cli.lint.only_errors {
+        (Some("error"), "warning", "--only-errors")
+    } else if cli.lint.only_warnings {
+        (Some("warning"), "error", "--only-warnings")
+    } else {
+        (None, "", "")
+    };
+    let mut hidden_by_severity = 0;
+    if let Some(severity) = shown_severity {
+        for file in &mut lint_results {
+            let before = file.diagnostics.len();
+            file.diagnostics
+                .retain(|diagnostic| diagnostic.severity == severity);
+            hidden_by_severity += before - file.diagnostics.len();
+        }
+    }
+    let shown_summary = (!cli.lint.no_summary).then_some(&summary);
+    match cli.lint.format {
+        _ if streaming => shown_summary.map_or(Ok(()), |summary| print_summary(&mut out, summary)),
+        OutputFormat::Text if cli.lint.group_by_rule => {
+            print_grouped_report(&mut out, &lint_results, shown_summary)
+        }
+        OutputFormat::Text => print_text_report(
+            &mut out,
+            &lint_results,
+            shown_summary,
+            cli.lint.max_issues_per_file,
+        ),
+        OutputFormat::Compact => print_compact_report(&mut out, &lint_results, shown_summary),
+        OutputFormat::Json => {
+            let failing: Vec<LintFileResult>;
+            let files = if cli.lint.only_failing {
+                failing = lint_results
+                    .iter()
+                    .filter(|file| !file.diagnostics.is_empty())
+                    .cloned()
+                    .collect();
+                &failing
+            } else {
+                &lint_results
+            };
+            writeln!(out, "{}", render_json_report(files, &summary, json_style)?)
+        }
+        OutputFormat::Codeclimate => {
+            let base = relative_to.as_deref().unwrap_or(&root);
+            writeln!(out, "{}", render_codeclimate_report(&lint_results, base)?)
+        }
+    }
+    .context("failed to write report")?;
+
+    let mut notices = Vec::new();
+    if hidden > 0 {
+        notices.push(format!(
+            "Hid {hidden} issue(s) without an autofix (--fixable-only)."
+        ));
+    }
+    if hidden_by_severity > 0 {
+        notices.push(format!(
+            "Hid {hidden_by_severity} {hidden_severity}(s) ({severity_flag})."
+        ));
+    }
+    if lint_results.len() < lint_inputs.len() {
+        notices.push(format!(
+            "Stopped after the first file with errors (--fail-fast); {} of {} file(s) linted.",
+            lint_results.len(),
+            lint_inputs.len()
+        ));
+    }
+    if cli.lint.stats && !cli.lint.group_by_rule {
+        notices.push("Issues by rule:".to_owned());
+        for (rule, count) in count_by_rule(&lint_results) {
+            notices.push(format!("  {rule}: {count}"));
+        }
+    }
+    if cli.lint.highlight_worst {
+        if let Some(line) = describe_worst_file(&lint_results) {
+            notices.push(line);
+        }
+    }
+
+    notices.extend(skipped);
+    if let Some(limit) = cli.lint.max_issues.filter(|limit| summary.issues > *limit) {
+        notices.push(format!(
+            "Found {} issue(s), over the --max-issues limit of {limit}.",
+            summary.issues
+        ));
+    }
+    for line in &notices {
+        print_notice(&mut out, cli.lint.format, line).context("failed to write report")?;
+    }
+    out.flush().context("failed to write report")?;
+
+    let failing = match config.fail_level {
+        FailLevel::Error => summary.errors,
+        FailLevel::Warning => summary.errors + summary.warnings,
+        FailLevel::Never => 0,
+    };
+    // An issue budget replaces the severity check; only the total counts.
+    let failed = match cli.lint.max_issues {
+        Some(limit) => summary.issues > limit,
+        None => failing > 0,
+    };
+    if failed || fail_on_hit {
+        Ok(ExitCode::LintIssues)
+    } else {
+        Ok(ExitCode::Clean)
+    }
+}
+
+/// Wall-clock phase durations for `--timings`. Printed on drop so runs that
+/// exit early (`--fix-dry-run`, `--quiet-clean`, `--baseline-diff`) still
+/// report them.
+struct Timings {
+    enabled: bool,
+    started: Instant,
+    collect: Duration,
+    read: Duration,
+    bridge: Duration,
+    /// Each bridge process's own time; with `--jobs` they overlap.
+    workers: Vec<Duration>,
+}
+
+impl Timings {
+    fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            started: Instant::now(),
+            collect: Duration::ZERO,
+            read: Duration::ZERO,
+            bridge: Duration::ZERO,
+            workers: Vec::new(),
+        }
+    }
+}
+
+impl Drop for Timings {
+    fn drop(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        let millis = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        eprintln!("timings:");
+        eprintln!("  collect: {:.1}ms", millis(self.collect));
+        eprintln!("  read: {:.1}ms", millis(self.read));
+        eprintln!("  bridge: {:.1}ms", millis(self.bridge));
+        if self.workers.len() > 1 {
+            for (index, worker) in self.workers.iter().enumerate() {
+                eprintln!("    worker {}: {:.1}ms", index + 1, millis(*worker));
+            }
+        }
+        eprintln!("  total: {:.1}ms", millis(self.started.elapsed()));
+    }
+}
+
+/// Where the human-readable report goes: `--output FILE`, or stdout.
+fn open_report_output(path: Option<&Path>) -> Result<Box<dyn Write>> {
+    let Some(path) = path else {
+        return Ok(Box::new(io::stdout()));
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create output directory '{}'", parent.display()))?;
+    }
+    let file = fs::File::create(path)
+        .with_context(|| format!("failed to create --output file '{}'", path.display()))?;
+    Ok(Box::new(io::BufWriter::new(file)))
+}
+
+/// Rewrites each `\n` written through it as `\r\n`, for `--eol crlf`.
+struct CrlfWriter<W: Write>(W);
+
+impl<W: Write> Write for CrlfWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for (index, line) in buf.split(|&byte| byte == b'\n').enumerate() {
+            if index > 0 {
+                self.0.write_all(b"\r\n")?;
+            }
+            self.0.write_all(line)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+fn print_text_report(
+    out: &mut dyn Write,
+    results: &[LintFileResult],
+    summary: Option<&LintSummary>,
+    max_per_file: Option<usize>,
+) -> io::Result<()> {
+    for file in results {
+        print_text_file(out, file, max_per_file)?;
+    }
+
+    summary.map_or(Ok(()), |summary| print_summary(out, summary))
+}
+
+fn print_text_file(
+    out: &mut dyn Write,
+    file: &LintFileResult,
+    max_per_file: Option<usize>,
+) -> io::Result<()> {
+    let shown = max_per_file.unwrap_or(usize::MAX);
+    for diagnostic in file.diagnostics.iter().take(shown) {
+        writeln!(
+            out,
+            "{}:{}:{}: {}: {}",
+            file.path,
+            diagnostic.range.start.line + 1,
+            diagnostic.range.start.character + 1,
+            diagnostic.severity,
+            diagnostic.message
+        )?;
+    }
+    if file.diagnostics.len() > shown {
+        writeln!(
+            out,
+            "{}: ... and {} more in this file.",
+            file.path,
+            file.diagnostics.len() - shown
+        )?;
+    }
+    Ok(())
+}
+
+/// `--group-by-rule`: one section per rule, most frequent first, with
+/// occurrences in path order.
+fn print_grouped_report(
+    out: &mut dyn Write,
+    results: &[LintFileResult],
+    summary: Option<&LintSummary>,
+) -> io::Result<()> {
+    let counts = count_by_rule(results);
+    for (rule, count) in &counts {
+        writeln!(out, "{rule} ({count} issue(s)):")?;
+        for file in results {
+            for diagnostic in &file.diagnostics {
+                if diagnostic.rule.as_deref().unwrap_or("unknown") != *rule {
+                    continue;
+                }
+                writeln!(
+                    out,
+                    "  {}:{}:{}: {}: {}",
+                    file.path,
+                    diagnostic.range.start.line + 1,
+                    diagnostic.range.start.character + 1,
+                    diagnostic.severity,
+                    diagnostic.message
+                )?;
+            }
+        }
+        writeln!(out)?;
+    }
+    if !counts.is_empty() {
+        writeln!(out, "Issues by rule:")?;
+        for (rule, count) in &counts {
+            writeln!(out, "  {rule}: {count}")?;
+        }
+    }
+
+    summary.map_or(Ok(()), |summary| print_summary(out, summary))
+}
+
+fn print_compact_report(
+    out: &mut dyn Write,
+    results: &[LintFileResult],
+    summary: Option<&LintSummary>,
+) -> io::Result<()> {
+    for file in results {
+        print_compact_file(out, file)?;
+    }
+
+    summary.map_or(Ok(()), |summary| print_summary(out, summary))
+}
+
+fn print_compact_file(out: &mut dyn Write, file: &LintFileResult) -> io::Result<()> {
+    let Some(first) = file.diagnostics.first() else {
+        return Ok(());
+    };
+    writeln!(
+        out,
+        "{}: {} issue(s) (first: {})",
+        file.path,
+        file.diagnostics.len(),
+        first.message
+    )
+}
+
+fn print_summary(out: &mut dyn Write, summary: &LintSummary) -> io::Result<()> {
+    writeln!(
+        out,
+        "Linted {} file(s): {} issue(s) ({} error(s), {} warning(s)).",
+        summary.files, summary.issues, summary.errors, summary.warnings
+    )
+}
+
+/// Prints a line that follows the report, keeping the report parseable when it
+/// carries JSON.
+fn print_notice(out: &mut dyn Write, format: OutputFormat, line: &str) -> io::Result<()> {
+    if format.is_json() {
+        eprintln!("{line}");
+        Ok(())
+    } else {
+        writeln!(out, "{line}")
+    }
+}
+
+/// Issue counts per rule ID, sorted by count (descending) and then by ID.
+fn count_by_rule(results: &[LintFileResult]) -> Vec<(&str, usize)> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for diagnostic in results.iter().flat_map(|file| &file.diagnostics) {
+        *counts
+            .entry(diagnostic.rule.as_deref().unwrap_or("unknown"))
+            .or_default() += 1;
+    }
+    let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    counts
+}
+
+/// The `--highlight-worst` line. `results` are sorted by path, and `max_by`
+/// keeps the last of equal elements, so the search runs in reverse.
+fn describe_worst_file(results: &[LintFileResult]) -> Option<String> {
+    let (worst, summary) = results
+        .iter()
+        .rev()
+        .map(|file| (file, LintSummary::from_results(std::slice::from_ref(file))))
+        .max_by_key(|(_, summary)| (summary.errors, summary.issues))?;
+    if summary.issues == 0 {
+        return None;
+    }
+    Some(format!(
+        "Most issues: {} ({} error(s), {} warning(s)).",
+        worst.path, summary.errors, summary.warnings
+    ))
+}
+
+/// How `--format json` and `--report` lay out their output.
+#[derive(Clone, Copy)]
+struct JsonStyle {
+    base: PositionBase,
+    /// The bare array of file results from before the report was versioned.
+    legacy: bool,
+}
+
+/// Renders results as `{"version", "files", "summary"}`, or as the bare
+/// `files` array for `--json-legacy`. The linter's positions are 0-based;
+/// with `PositionBase::One` lines and characters are shifted to match the
+/// text output. Byte offsets are always 0-based.
+fn render_json_report(
+    results: &[LintFileResult],
+    summary: &LintSummary,
+    style: JsonStyle,
+) -> Result<String> {
+    let mut value = serde_json::to_value(results).context("failed to serialize JSON report")?;
+    if style.base == PositionBase::One {
+        let diagnostics = value
+            .as_array_mut()
+            .into_iter()
+            .flatten()
+            .filter_map(|file| file["diagnostics"].as_array_mut())
+            .flatten();
+        for diagnostic in diagnostics {
+            for bound in ["start", "end"] {
+                let Some(position) = diagnostic["range"][bound].as_object_mut() else {
+                    continue;
+                };
+                for field in ["line", "character"] {
+                    if let Some(number) = position.get(field).and_then(|number| number.as_u64()) {
+                        position.insert(field.to_owned(), (number + 1).into());
+                    }
+                }
+            }
+        }
+    }
+    if !style.legacy {
+        value = serde_json::json!({
+            "version": JSON_REPORT_VERSION,
+            "files": value,
+            "summary": summary,
+        });
+    }
+    serde_json::to_string_pretty(&value).context("failed to serialize JSON report")
+}
+
+/// Runs `linter` and then each of `extra` over the same files, merging their
+/// diagnostics. One reported by several linters is kept once, from the first
+/// that reported it; `--verbose` logs which linter each one came from.
+/// Markdown files are linted block by block; see [`markdown::split_blocks`].
+/// Each bridge's time is appended to `worker_times`.
+fn run_linters(
+    linter: &LinterOptions,
+    extra: &[LinterOptions],
+    inputs: &[LintInputFile],
+    jobs: usize,
+    max_inflight: usize,
+    worker_times: &mut Vec<Duration>,
+) -> Result<Vec<LintFileResult>> {
+    let blocks = markdown::split_blocks(inputs);
+    let mut run = |linter: &LinterOptions| -> Result<Vec<LintFileResult>> {
+        let (results, durations) = run_node_linter_chunked(linter, &blocks, jobs, max_inflight)?;
+        worker_times.extend(durations);
+        Ok(markdown::merge_blocks(inputs, results))
+    };
+    let mut results = run(linter)?;
+    if extra.is_empty() {
+        return Ok(results);
+    }
+    for file in &results {
+        for diagnostic in &file.diagnostics {
+            log_linter_source(linter, &file.path, diagnostic);
+        }
+    }
+    for other in extra {
+        for result in run(other)? {
+            let Some(file) = results.iter_mut().find(|file| file.path == result.path) else {
+                results.push(result);
+                continue;
+            };
+            for diagnostic in result.diagnostics {
+                if file
+                    .diagnostics
+                    .iter()
+                    .any(|seen| same_diagnostic(seen, &diagnostic))
+                {
+                    continue;
+                }
+                log_linter_source(other, &file.path, &diagnostic);
+                file.diagnostics.push(diagnostic);
+            }
+        }
+    }
+    for file in &mut results {
+        file.diagnostics.sort_by_key(|diagnostic| {
+            (
+                diagnostic.range.start.line,
+                diagnostic.range.start.character,
+            )
+        });
+    }
+    Ok(results)
+}
+
+fn same_diagnostic(a: &LintDiagnostic, b: &LintDiagnostic) -> bool {
+    a.severity == b.severity
+        && a.rule == b.rule
+        && a.message == b.message
+        && a.range.start.line == b.range.start.line
+        && a.range.start.character == b.range.start.character
+}
+
+fn log_linter_source(linter: &LinterOptions, path: &str, diagnostic: &LintDiagnostic) {
+    linter.log.info(|| {
+        let source = match &linter.path {
+            Some(path) => format!("'{}'", path.display()),
+            None => "embedded".to_owned(),
+        };
+        format!(
+            "linter {source}: {path}:{}:{}: {}",
+            diagnostic.range.start.line + 1,
+            diagnostic.range.start.character + 1,
+            diagnostic.message
+        )
+    });
+}
+
+/// Writes the files as the bridge receives them on stdin. Chunked runs split
+/// this array across processes, but each chunk is serialized the same way.
+fn dump_payload(path: &Path, inputs: &[LintInputFile]) -> Result<()> {
+    let payload = serde_json::to_vec(inputs).context("failed to serialize lint payload")?;
+    if path == Path::new("-") {
+        let mut stderr = io::stderr().lock();
+        stderr.write_all(&payload)?;
+        writeln!(stderr)?;
+        return Ok(());
+    }
+    fs::write(path, payload)
+        .with_context(|| format!("failed to write payload dump '{}'", path.display()))
+}
+
+/// Renders one Code Climate issue per diagnostic. The fingerprint hashes the
+/// path, rule, message, and how many identical issues came before it in the
+/// file, so it survives edits elsewhere in the file, like baseline keys do.
+fn render_codeclimate_report(results: &[LintFileResult], base: &Path) -> Result<String> {
+    let mut issues = Vec::new();
+    for file in results {
+        let path = Path::new(&file.path);
+        let path = if path.is_absolute() {
+            display_path(&relative_path(path, base))
+        } else {
+            file.path.clone()
+        };
+        let mut seen: HashMap<(&str, &str), usize> = HashMap::new();
+        for diagnostic in &file.diagnostics {
+            let rule = diagnostic.rule.as_deref().unwrap_or("unknown");
+            let occurrence = seen.entry((rule, &diagnostic.message)).or_default();
+            let fingerprint = fnv1a_64(
+                format!("{path}\0{rule}\0{}\0{occurrence}", diagnostic.message).as_bytes(),
+            );
+            *occurrence += 1;
+            let (severity, category) = if diagnostic.severity == "error" {
+                ("major", "Bug Risk")
+            } else {
+                ("minor", "Style")
+            };
+            issues.push(serde_json::json!({
+                "type": "issue",
+                "check_name": rule,
+                "description": diagnostic.message,
+                "categories": [category],
+                "severity": severity,
+                "fingerprint": format!("{fingerprint:016x}"),
+                "location": {
+                    "path": path,
+                    "lines": { "begin": diagnostic.range.start.line + 1 },
+                },
+            }));
+        }
+    }
+    serde_json::to_string_pretty(&issues).context("failed to serialize Code Climate report")
+}
+
+fn write_json_report(
+    path: &Path,
+    results: &[LintFileResult],
+    summary: &LintSummary,
+    style: JsonStyle,
+) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create report directory '{}'", parent.display()))?;
+    }
+    let mut json = render_json_report(results, summary, style)?;
+    json.push('\n');
+    fs::write(path, json)
+        .with_context(|| format!("failed to write --report file '{}'", path.display()))
+}
+
+/// Identifies a diagnostic across runs. Positions are left out so edits
+/// elsewhere in a file do not turn known issues into new ones.
+type BaselineKey = (String, Option<String>, String);
+
+/// A `--baseline` file: a versioned `--report`, or a legacy bare array.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BaselineFile {
+    Versioned { files: Vec<LintFileResult> },
+    Legacy(Vec<LintFileResult>),
+}
+
+fn read_baseline(path: &Path) -> Result<Vec<LintFileResult>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read --baseline file '{}'", path.display()))?;
+    let baseline = serde_json::from_str(&contents)
+        .with_context(|| format!("failed to parse --baseline file '{}'", path.display()))?;
+    Ok(match baseline {
+        BaselineFile::Versioned { files } | BaselineFile::Legacy(files) => files,
+    })
+}
+
+/// Removes diagnostics recorded in `baseline` from `results`, matching each
+/// baseline entry at most once. Returns the baseline entries that no longer
+/// occur.
+fn subtract_baseline(
+    results: &mut [LintFileResult],
+    baseline: &[LintFileResult],
+) -> Vec<BaselineKey> {
+    let mut known: BTreeMap<BaselineKey, usize> = BTreeMap::new();
+    for file in baseline {
+        for diagnostic in &file.diagnostics {
+            let key = (
+                file.path.clone(),
+                diagnostic.rule.clone(),
+                diagnostic.message.clone(),
+            );
+            *known.entry(key).or_default() += 1;
+        }
+    }
+
+    for file in results.iter_mut() {
+        file.diagnostics.retain(|diagnostic| {
+            let key = (
+                file.path.clone(),
+                diagnostic.rule.clone(),
+                diagnostic.message.clone(),
+            );
+            match known.get_mut(&key) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            }
+        });
+    }
+
+    known
+        .into_iter()
+        .flat_map(|(key, count)| std::iter::repeat_n(key, count))
+        .collect()
+}
+
+fn print_baseline_diff(
+    out: &mut dyn Write,
+    new: &[LintFileResult],
+    resolved: &[BaselineKey],
+) -> io::Result<ExitCode> {
+    let new_count: usize = new.iter().map(|file| file.diagnostics.len()).sum();
+    writeln!(out, "new={new_count} resolved={}", resolved.len())?;
+    for file in new {
+        print_text_file(out, file, None)?;
+    }
+    for (path, _, message) in resolved {
+        writeln!(out, "resolved: {path}: {message}")?;
+    }
+    out.flush()?;
+
+    if new_count > 0 {
+        Ok(ExitCode::LintIssues)
+    } else {
+        Ok(ExitCode::Clean)
+    }
+}
+
+fn annotate_offsets(results: &mut [LintFileResult], inputs: &[LintInputFile]) {
+    for result in results {
+        let Some(input) = inputs.iter().find(|input| input.path == result.path) else {
+            continue;
+        };
+        // Offsets are into the file on disk, which still has the stripped BOM.
+        let base = if input.bom { UTF8_BOM.len_utf8() } else { 0 };
+        for diagnostic in &mut result.diagnostics {
+            let range = &mut diagnostic.range;
+            range.start.offset = Some(base + position_to_offset(&input.source, &range.start));
+            if let Some(end) = &mut range.end {
+                end.offset = Some(base + position_to_offset(&input.source, end));
+            }
+        }
+    }
+}
+
+/// Parses arguments with a `--version` string that also identifies the embedded
+/// linter, so two installs can be compared without diffing binaries.
+fn parse_cli() -> Result<Cli> {
+    let version = format!(
+        "{} (embedded linter fnv1a-64 {:016x})",
+        env!("CARGO_PKG_VERSION"),
+        fnv1a_64(EMBEDDED_LINTER_SOURCE.as_bytes())
+    );
+    let args = expand_response_files(std::env::args_os())?;
+    let matches = Cli::command()
+        .version(&*Box::leak(version.into_boxed_str()))
+        .get_matches_from(args);
+    Ok(Cli::from_arg_matches(&matches).unwrap_or_else(|error| error.exit()))
+}
+
+/// Replaces each `@FILE` argument with the arguments read from FILE, so long
+/// glob lists can bypass command-length limits. Response files cannot
+/// reference other response files.
+fn expand_response_files(args: impl IntoIterator<Item = OsString>) -> Result<Vec<OsString>> {
+    let mut expanded = Vec::new();
+    for (index, arg) in args.into_iter().enumerate() {
+        let response_file = match arg.to_str() {
+            Some(text) if index > 0 && text.len() > 1 => text.strip_prefix('@'),
+            _ => None,
+        };
+        let Some(path) = response_file else {
+            expanded.push(arg);
+            continue;
+        };
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read response file '{path}'"))?;
+        for word in split_response_file(&contents) {
+            if word.starts_with('@') {
+                bail!("response file '{path}' references '{word}'; nested response files are not supported");
+            }
+            expanded.push(word.into());
+        }
+    }
+    Ok(expanded)
+}
+
+/// Splits on whitespace. Single or double quotes group a word and are removed,
+/// so `--glob '**/*.julietscript'` reads the same as it would in a shell.
+fn split_response_file(contents: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    for ch in contents.chars() {
+        match quote {
+            Some(open) if ch == open => quote = None,
+            Some(_) => word.push(ch),
+            None if ch == '\'' || ch == '"' => {
+                quote = Some(ch);
+                in_word = true;
+            }
+            None if ch.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            None => {
+                word.push(ch);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+/// Fingerprints everything besides a file's contents that shapes its
+/// diagnostics, so `--cache` entries are dropped when any of it changes.
+fn cache_key(linter: &LinterOptions) -> Result<String> {
+    let source = match &linter.path {
+        Some(path) => fs::read_to_string(path)
+            .with_context(|| format!("failed to read linter '{}'", path.display()))?,
+        None => EMBEDDED_LINTER_SOURCE.to_owned(),
+    };
+    let rules = serde_json::to_string(&linter.rules).context("failed to serialize rule options")?;
+    let fingerprint = format!("{}\n{rules}\n{source}", env!("CARGO_PKG_VERSION"));
+    Ok(format!("{:016x}", fnv1a_64(fingerprint.as_bytes())))
+}
+
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+fn print_linter(linter_path: Option<&Path>, allow_embedded: bool) -> Result<()> {
+    match linter_path {
+        Some(path) => {
+            let source = fs::read_to_string(path)
+                .with_context(|| format!("failed to read linter '{}'", path.display()))?;
+            print!("{source}");
+        }
+        None => {
+            ensure_embedded_allowed(allow_embedded)?;
+            print!("{EMBEDDED_LINTER_SOURCE}");
+        }
+    }
+    Ok(())
+}
+
+/// Runtimes `doctor` looks for. Only `node` can run the linter bridge; the
+/// others are reported so users can tell what is installed.
+const DOCTOR_RUNTIMES: &[(&str, bool)] = &[("node", true), ("deno", false), ("bun", false)];
+
+fn run_doctor(args: DoctorArgs) -> Result<ExitCode> {
+    let mut healthy = true;
+    for (runtime, required) in DOCTOR_RUNTIMES {
+        match runtime_version(runtime) {
+            Some(version) => println!("PASS {runtime}: {version}"),
+            None if *required => {
+                healthy = false;
+                println!("FAIL {runtime}: not found on PATH; install Node.js 18+ to run julietscript-lint");
+            }
+            None => println!("SKIP {runtime}: not found on PATH (optional)"),
+        }
+    }
+
+    let linter = LinterOptions {
+        path: resolve_linter_path(args.linter)?,
+        allow_embedded: true,
+        rules: RuleSelection {
+            enable: Vec::new(),
+            disable: Vec::new(),
+            max_line_length: None,
+            max_rubric_points: None,
+            exact_rubric_points: None,
+            max_search_budget: None,
+            external_artifacts: false,
+            check_sources: false,
+            profile: None,
+        },
+        log: Logger { verbosity: 0 },
+        spawn_retries: DEFAULT_SPAWN_RETRIES,
+        node_args: Vec::new(),
+    };
+    let label = match &linter.path {
+        Some(path) => format!("linter '{}'", path.display()),
+        None => "embedded linter".to_owned(),
+    };
+    let sample = LintInputFile {
+        path: "doctor.julietscript".to_owned(),
+        source: "halt;\n".to_owned(),
+        file: PathBuf::from("doctor.julietscript"),
+        bom: false,
+    };
+    match run_node_linter(&linter, std::slice::from_ref(&sample)) {
+        Ok(results) if results.len() == 1 && results[0].diagnostics.is_empty() => {
+            println!("PASS {label}: linted a sample script cleanly");
+        }
+        Ok(results) => {
+            healthy = false;
+            let messages: Vec<&str> = results
+                .iter()
+                .flat_map(|result| &result.diagnostics)
+                .map(|diagnostic| diagnostic.message.as_str())
+                .collect();
+            println!(
+                "FAIL {label}: unexpected result for a clean sample script: {}",
+                messages.join("; ")
+            );
+        }
+        Err(error) => {
+            healthy = false;
+            println!("FAIL {label}: {error:#}");
+        }
+    }
+
+    if !healthy {
+        bail!("this environment cannot lint JulietScript files");
+    }
+    Ok(ExitCode::Clean)
+}
+
+/// First line of `<runtime> --version`, or `None` when it cannot be run.
+fn runtime_version(runtime: &str) -> Option<String> {
+    let output = std::process::Command::new(runtime)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout.lines().next().map(|line| line.trim().to_owned())
+}
+
+fn print_example() {
+    print!("{EXAMPLE_SCRIPT}");
+}
+
+fn write_template(args: &NewArgs) -> Result<()> {
+    if args.path.exists() && !args.force {
+        bail!(
+            "'{}' already exists. Pass --force to overwrite it",
+            args.path.display()
+        );
+    }
+    if let Some(parent) = args.path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory '{}'", parent.display()))?;
+    }
+    fs::write(&args.path, args.template.source())
+        .with_context(|| format!("failed to write '{}'", args.path.display()))?;
+    let template = args
+        .template
+        .to_possible_value()
+        .expect("templates are never skipped");
+    println!(
+        "Wrote {} template to '{}'.",
+        template.get_name(),
+        args.path.display()
+    );
+    Ok(())
+}
+
+#[derive(Deserialize, Serialize)]
+struct AstFileResult {
+    path: String,
+    ast: Option<serde_json::Value>,
+    #[serde(default, skip_serializing)]
+    diagnostics: Vec<LintDiagnostic>,
+}
+
+fn print_ast(linter: &LinterOptions, inputs: &[LintInputFile]) -> Result<ExitCode> {
+    let results = run_node_bridge::<AstFileResult>(linter, inputs, "ast")?;
+    // The bridge reports a linter crash as a diagnostic in place of the tree.
+    if let Some(result) = results.iter().find(|result| result.ast.is_none()) {
+        let reason = result
+            .diagnostics
+            .first()
+            .map_or("no parse tree returned", |diagnostic| {
+                diagnostic.message.as_str()
+            });
+        bail!("failed to parse '{}': {reason}", result.path);
+    }
+    let rendered =
+        serde_json::to_string_pretty(&results).context("failed to serialize parse trees")?;
+    println!("{rendered}");
+    Ok(ExitCode::Clean)
+}
+
+#[derive(Deserialize)]
+struct GraphFileResult {
+    diagnostics: Vec<LintDiagnostic>,
+    artifacts: Vec<ArtifactNode>,
+}
+
+#[derive(Deserialize)]
+struct ArtifactNode {
+    name: String,
+    using: Vec<String>,
+}
+
+fn print_graph(args: GraphArgs) -> Result<ExitCode> {
+    let inputs = load_files(std::slice::from_ref(&args.path))?;
+    let linter = LinterOptions {
+        path: resolve_linter_path(args.linter)?,
+        allow_embedded: true,
+        rules: RuleSelection {
+            enable: Vec::new(),
+            disable: Vec::new(),
+            max_line_length: None,
+            max_rubric_points: None,
+            exact_rubric_points: None,
+            max_search_budget: None,
+            external_artifacts: false,
+            check_sources: false,
+            profile: None,
+        },
+        log: Logger { verbosity: 0 },
+        spawn_retries: DEFAULT_SPAWN_RETRIES,
+        node_args: Vec::new(),
+    };
+    let result = run_node_bridge::<GraphFileResult>(&linter, &inputs, "graph")?
+        .pop()
+        .context("node bridge returned no results")?;
+
+    // An unresolved `using` would produce a dangling edge, so only graph
+    // scripts without lint errors.
+    let errors: Vec<&LintDiagnostic> = result
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == "error")
+        .collect();
+    if !errors.is_empty() {
+        for diagnostic in &errors {
+            eprintln!(
+                "{}:{}:{}: error: {}",
+                inputs[0].path,
+                diagnostic.range.start.line + 1,
+                diagnostic.range.start.character + 1,
+                diagnostic.message
+            );
+        }
+        eprintln!(
+            "julietscript-lint: not printing graph: {} error(s) in '{}'.",
+            errors.len(),
+            inputs[0].path
+        );
+        return Ok(ExitCode::LintIssues);
+    }
+
+    match args.format {
+        GraphFormat::Dot => print!("{}", render_dot(&result.artifacts)),
+        GraphFormat::Json => {
+            let mut dependents: BTreeMap<&str, Vec<&str>> = result
+                .artifacts
+                .iter()
+                .map(|artifact| (artifact.name.as_str(), Vec::new()))
+                .collect();
+            for artifact in &result.artifacts {
+                for dependency in &artifact.using {
+                    dependents
+                        .entry(dependency.as_str())
+                        .or_default()
+                        .push(artifact.name.as_str());
+                }
+            }
+            let rendered = serde_json::to_string_pretty(&dependents)
+                .context("failed to serialize artifact graph")?;
+            println!("{rendered}");
+        }
+    }
+    Ok(ExitCode::Clean)
+}
+
+/// Edges point from a `using` dependency to the artifact that consumes it.
+fn render_dot(artifacts: &[ArtifactNode]) -> String {
+    let mut dot = String::from("digraph artifacts {\n");
+    for artifact in artifacts {
+        dot.push_str(&format!("  {};\n", artifact.name));
+    }
+    for artifact in artifacts {
+        for dependency in &artifact.using {
+            dot.push_str(&format!("  {} -> {};\n", dependency, artifact.name));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+fn print_fix_previews(
+    inputs: &[LintInputFile],
+    results: &[LintFileResult],
+    only: &[String],
+) -> ExitCode {
+    let mut has_fixes = false;
+    for result in results {
+        let Some(input) = inputs.iter().find(|input| input.path == result.path) else {
+            continue;
+        };
+        if let Some(fixed) = apply_fixes(&input.source, &result.diagnostics, only) {
+            has_fixes = true;
+            print!("{}", unified_diff(&input.path, &input.source, &fixed));
+        }
+    }
+
+    if has_fixes {
+        ExitCode::LintIssues
+    } else {
+        ExitCode::Clean
+    }
+}
+
+/// Editor format-on-save round-trip: the fixed script goes to stdout and
+/// whatever `--fix` could not resolve is reported on stderr.
+fn fix_stdin(
+    linter: &LinterOptions,
+    inputs: &mut [LintInputFile],
+    mut results: Vec<LintFileResult>,
+    only: &[String],
+    error_on: &[String],
+) -> Result<ExitCode> {
+    let fixed = results
+        .first()
+        .and_then(|result| apply_fixes(&inputs[0].source, &result.diagnostics, only));
+    if let Some(fixed) = fixed {
+        inputs[0].source = fixed;
+        results = run_node_linter(linter, inputs)?;
+    }
+    for file in &mut results {
+        normalize_messages(file);
+        elevate_rules(file, error_on);
+    }
+    if results.iter().any(|file| !file.diagnostics.is_empty()) {
+        print_text_report(&mut io::stderr().lock(), &results, None, None)
+            .context("failed to write diagnostics")?;
+    }
+
+    let input = &inputs[0];
+    let mut stdout = io::stdout().lock();
+    if input.bom {
+        write!(stdout, "{UTF8_BOM}").context("failed to write fixed source")?;
+    }
+    stdout
+        .write_all(input.source.as_bytes())
+        .and_then(|()| stdout.flush())
+        .context("failed to write fixed source")?;
+    Ok(ExitCode::Clean)
+}
+
+fn write_fixes(
+    inputs: &mut [LintInputFile],
+    results: &[LintFileResult],
+    only: &[String],
+) -> Result<usize> {
+    let mut fixed_count = 0usize;
+    for result in results {
+        let Some(input) = inputs.iter_mut().find(|input| input.path == result.path) else {
+            continue;
+        };
+        if let Some(fixed) = apply_fixes(&input.source, &result.diagnostics, only) {
+            let contents = if input.bom {
+                format!("{UTF8_BOM}{fixed}")
+            } else {
+                fixed.clone()
+            };
+            fs::write(&input.file, contents)
+                .with_context(|| format!("failed to write fixes to '{}'", input.path))?;
+            input.source = fixed;
+            fixed_count += 1;
+        }
+    }
+    Ok(fixed_count)
+}
+
+/// Applies every non-overlapping fix attached to `diagnostics`, returning `None`
+/// when there is nothing to fix. Overlapping edits are left for a later run.
+/// A non-empty `only` restricts fixing to those rule IDs.
+fn apply_fixes(source: &str, diagnostics: &[LintDiagnostic], only: &[String]) -> Option<String> {
+    let mut edits: Vec<(usize, usize, &str)> = diagnostics
+        .iter()
+        .filter(|diagnostic| {
+            only.is_empty()
+                || diagnostic
+                    .rule
+                    .as_ref()
+                    .is_some_and(|rule| only.contains(rule))
+        })
+        .filter_map(|diagnostic| diagnostic.fix.as_ref())
+        .map(|fix| {
+            (
+                position_to_offset(source, &fix.range.start),
+                position_to_offset(source, &fix.range.end),
+                fix.text.as_str(),
+            )
+        })
+        .collect();
+    if edits.is_empty() {
+        return None;
+    }
+    edits.sort();
+    edits.dedup();
+
+    let mut fixed = String::with_capacity(source.len());
+    let mut cursor = 0usize;
+    for (start, end, text) in edits {
+        if start < cursor {
+            continue;
+        }
+        fixed.push_str(&source[cursor..start]);
+        fixed.push_str(text);
+        cursor = end;
+    }
+    fixed.push_str(&source[cursor..]);
+    Some(fixed)
+}
+
+/// Converts a linter position (0-based line, UTF-16 column) into a byte offset.
+fn position_to_offset(source: &str, position: &LintPosition) -> usize {
+    let mut line_start = 0usize;
+    for _ in 0..position.line {
+        match source[line_start..].find('\n') {
+            Some(index) => line_start += index + 1,
+            None => return source.len(),
+        }
+    }
+
+    let mut units = 0usize;
+    for (offset, ch) in source[line_start..].char_indices() {
+        if units >= position.character || ch == '\n' {
+            return line_start + offset;
+        }
+        units += ch.len_utf16();
+    }
+    source.len()
+}
+
+const DIFF_CONTEXT_LINES: usize = 3;
+
+#[derive(Clone, Copy)]
+enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+fn unified_diff(path: &str, original: &str, fixed: &str) -> String {
+    let old_lines: Vec<&str> = original.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = fixed.split_inclusive('\n').collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    // Line numbers in the old and new file at the start of each op.
+    let mut positions = Vec::with_capacity(ops.len() + 1);
+    let (mut old_line, mut new_line) = (0usize, 0usize);
+    for op in &ops {
+        positions.push((old_line, new_line));
+        match op {
+            DiffLine::Same(_) => {
+                old_line += 1;
+                new_line += 1;
+            }
+            DiffLine::Removed(_) => old_line += 1,
+            DiffLine::Added(_) => new_line += 1,
+        }
+    }
+    positions.push((old_line, new_line));
+
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| !matches!(op, DiffLine::Same(_)))
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut output = format!("--- {path}\n+++ {path}\n");
+    let mut index = 0usize;
+    while index < changed.len() {
+        let start = changed[index].saturating_sub(DIFF_CONTEXT_LINES);
+        let mut last_change = changed[index];
+        while index + 1 < changed.len()
+            && changed[index + 1] <= last_change + 2 * DIFF_CONTEXT_LINES + 1
+        {
+            index += 1;
+            last_change = changed[index];
+        }
+        index += 1;
+        let end = (last_change + DIFF_CONTEXT_LINES + 1).min(ops.len());
+
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_end - old_start),
+            hunk_range(new_start, new_end - new_start)
+        ));
+        for op in &ops[start..end] {
+            let (prefix, line) = match op {
+                DiffLine::Same(line) => (' ', line),
+                DiffLine::Removed(line) => ('-', line),
+                DiffLine::Added(line) => ('+', line),
+            };
+            output.push(prefix);
+            output.push_str(line);
+            if !line.ends_with('\n') {
+                output.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+    output
+}
+
+fn hunk_range(start: usize, count: usize) -> String {
+    if count == 0 {
+        format!("{start},0")
+    } else {
+        format!("{},{}", start + 1, count)
+    }
+}
+
+/// Line diff based on a longest-common-subsequence table. Common leading and
+/// trailing lines are trimmed first, so fix previews stay cheap on large files.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffLine<'a>> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut lcs = vec![vec![0usize; new_middle.len() + 1]; old_middle.len() + 1];
+    for i in (0..old_middle.len()).rev() {
+        for j in (0..new_middle.len()).rev() {
+            lcs[i][j] = if old_middle[i] == new_middle[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops: Vec<DiffLine<'a>> = old[..prefix]
+        .iter()
+        .map(|line| DiffLine::Same(line))
+        .collect();
+    let (mut i, mut j) = (0usize, 0usize);
+    while i < old_middle.len() && j < new_middle.len() {
+        if old_middle[i] == new_middle[j] {
+            ops.push(DiffLine::Same(old_middle[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(DiffLine::Removed(old_middle[i]));
+            i += 1;
+        } else {
+            ops.push(DiffLine::Added(new_middle[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old_middle[i..].iter().map(|line| DiffLine::Removed(line)));
+    ops.extend(new_middle[j..].iter().map(|line| DiffLine::Added(line)));
+    ops.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| DiffLine::Same(line)),
+    );
+    ops
+}
+
+/// Reads `--stdin` documents: all of stdin as one script, or one
+/// `{"path": ..., "source": ...}` object per line for `ndjson`.
+fn read_stdin_inputs(format: StdinFormat, filename: Option<&str>) -> Result<Vec<LintInputFile>> {
+    let mut text = String::new();
+    io::stdin()
+        .read_to_string(&mut text)
+        .context("failed to read stdin")?;
+    let documents = match format {
+        StdinFormat::Raw => vec![LintInputFile {
+            path: filename.unwrap_or("<stdin>").to_owned(),
+            source: text,
+            file: PathBuf::new(),
+            bom: false,
+        }],
+        StdinFormat::Ndjson => text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str::<LintInputFile>(line)
+                    .with_context(|| format!("invalid NDJSON document on stdin line {}", index + 1))
+            })
+            .collect::<Result<Vec<_>>>()?,
+    };
+    if documents.is_empty() {
+        bail!("--stdin-format ndjson read no documents");
+    }
+    Ok(documents
+        .into_iter()
+        .map(|mut input| {
+            if let Some(rest) = input.source.strip_prefix(UTF8_BOM) {
+                input.source = rest.to_owned();
+                input.bom = true;
+            }
+            input.file = PathBuf::from(&input.path);
+            input
+        })
+        .collect())
+}
+
+/// Reports `julietArtifactSourceFiles` paths that do not exist for
+/// `--check-sources`. Paths resolve against the directory of the script that
+/// names them, not `--root`, so nested scripts can seed from their siblings.
+fn check_sources(file: &mut LintFileResult, inputs: &[LintInputFile]) {
+    let Some(input) = inputs.iter().find(|input| input.path == file.path) else {
+        return;
+    };
+    let base = input.file.parent().unwrap_or(Path::new(""));
+    for source in std::mem::take(&mut file.sources) {
+        if base.join(&source.path).exists() {
+            continue;
+        }
+        file.diagnostics.push(LintDiagnostic {
+            severity: "error".to_owned(),
+            message: format!(
+                "Source file '{}' does not exist relative to this script's directory.",
+                source.path
+            ),
+            rule: Some("missing-source".to_owned()),
+            range: source.range,
+            fix: None,
+        });
+    }
+    file.diagnostics.sort_by_key(|diagnostic| {
+        (
+            diagnostic.range.start.line,
+            diagnostic.range.start.character,
+        )
+    });
+}
+
+/// Trims each message and ends it with exactly one period (or its own `?` or
+/// `!`), so output reads the same whichever linter produced it. Idempotent,
+/// like `elevate_rules`.
+fn normalize_messages(file: &mut LintFileResult) {
+    for diagnostic in &mut file.diagnostics {
+        let message = diagnostic.message.trim().trim_end_matches('.').trim_end();
+        diagnostic.message = if message.is_empty() || message.ends_with(['?', '!']) {
+            message.to_owned()
+        } else {
+            format!("{message}.")
+        };
+    }
+}
+
+/// Reports `--error-on` rules as errors. Applying it twice is harmless, which
+/// lets streamed results be elevated before they print.
+fn elevate_rules(file: &mut LintFileResult, rules: &[String]) {
+    for diagnostic in &mut file.diagnostics {
+        if diagnostic
+            .rule
+            .as_ref()
+            .is_some_and(|rule| rules.contains(rule))
+        {
+            diagnostic.severity = "error".to_owned();
+        }
+    }
+}
+
+/// Renders a path for a notice, relative to the display base when there is one.
+fn render_path(path: &Path, relative_to: Option<&Path>) -> String {
+    match relative_to {
+        Some(base) => display_path(&relative_path(path, base)),
+        None => display_path(path),
+    }
+}
+
+/// Expresses `path` relative to `base`, climbing out with `..` when it lies
+/// outside. Both paths are expected to be canonical.
+fn relative_path(path: &Path, base: &Path) -> PathBuf {
+    let path_parts: Vec<_> = path.components().collect();
+    let base_parts: Vec<_> = base.components().collect();
+    let shared = path_parts
+        .iter()
+        .zip(&base_parts)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut relative: PathBuf = base_parts[shared..].iter().map(|_| "..").collect();
+    relative.extend(&path_parts[shared..]);
+    relative
+}
+
+/// Reads the `--input-list` file. Every missing entry is reported, not just the
+/// first, so CI logs show the whole problem at once.
+fn read_input_list(root: &Path, list: &Path, canonicalize: bool) -> Result<Vec<PathBuf>> {
+    let contents = fs::read_to_string(list)
+        .with_context(|| format!("failed to read --input-list '{}'", list.display()))?;
+    let mut files = BTreeSet::new();
+    let mut missing = Vec::new();
+    for entry in contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+    {
+        let path = root.join(entry);
+        if !path.is_file() {
+            missing.push(format!("  {entry}: no such file"));
+            continue;
+        }
+        files.insert(if canonicalize {
+            fs::canonicalize(&path).context("failed to canonicalize listed path")?
+        } else {
+            path
+        });
+    }
+    if !missing.is_empty() {
+        bail!(
+            "--input-list '{}' references missing files:\n{}",
+            list.display(),
+            missing.join("\n")
+        );
+    }
+    Ok(files.into_iter().collect())
+}
+
+fn find_unmatched_files(
+    root: &Path,
+    extension_pattern: &str,
+    matched: &[PathBuf],
+    log: Logger,
+    canonicalize: bool,
+) -> Result<Vec<PathBuf>> {
+    let candidates = collect_files(root, &[extension_pattern.to_owned()], log, canonicalize)?;
+    Ok(candidates
+        .into_iter()
+        .filter(|path| matched.binary_search(path).is_err())
+        .collect())
+}
+
+/// Canonicalizes `dir`, or under `--no-canonicalize` only makes it absolute
+/// so symlinks in it survive into reported paths.
+fn resolve_dir(dir: &Path, canonicalize: bool) -> io::Result<PathBuf> {
+    if canonicalize {
+        fs::canonicalize(dir)
+    } else if dir.is_dir() {
+        std::path::absolute(dir)
+    } else {
+        Err(io::Error::new(io::ErrorKind::NotFound, "not a directory"))
+    }
+}
+
+fn resolve_linter_path(linter_arg: Option<PathBuf>) -> Result<Option<PathBuf>> {
+    if let Some(path) = linter_arg {
+        if !path.is_file() {
+            bail!("--linter path '{}' is not a file", path.display());
+        }
+        return fs::canonicalize(path)
+            .context("failed to canonicalize --linter path")
+            .map(Some);
+    }
+
+    if let Some(env_path) = std::env::var_os("JULIETSCRIPT_LINTER_PATH") {
+        let path = PathBuf::from(env_path);
+        if !path.is_file() {
+            bail!(
+                "JULIETSCRIPT_LINTER_PATH '{}' is not a file",
+                path.display()
+            );
+        }
+        return fs::canonicalize(path)
+            .context("failed to canonicalize JULIETSCRIPT_LINTER_PATH")
+            .map(Some);
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_response_file_handles_quotes_and_blank_lines() {
+        assert_eq!(
+            split_response_file("--glob '**/*.julietscript'\n\n  --glob \"a b/*.jls\"\n--stats"),
+            vec![
+                "--glob",
+                "**/*.julietscript",
+                "--glob",
+                "a b/*.jls",
+                "--stats"
+            ]
+        );
+        assert_eq!(split_response_file("--root ''"), vec!["--root", ""]);
+        assert!(split_response_file(" \n\t").is_empty());
+    }
 }
@@ -0,0 +1,157 @@
+//! Linting JulietScript embedded in Markdown. Files ending in `.md` or
+//! `.markdown` are split into their fenced blocks tagged `juliet` or
+//! `julietscript`, each linted on its own, and the results merged back into
+//! one entry per Markdown file.
+
+use std::path::Path;
+
+use julietscript_lint::{LintFileResult, LintInputFile};
+
+const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown"];
+const BLOCK_LANGUAGES: &[&str] = &["juliet", "julietscript"];
+
+pub fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            MARKDOWN_EXTENSIONS
+                .iter()
+                .any(|known| extension.eq_ignore_ascii_case(known))
+        })
+}
+
+/// Replaces each Markdown input with one input per JulietScript block. A
+/// block's source is padded with the blank lines before it, so diagnostics
+/// land on the Markdown file's own line numbers without remapping.
+pub fn split_blocks(inputs: &[LintInputFile]) -> Vec<LintInputFile> {
+    let mut split = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        if !is_markdown(&input.file) {
+            split.push(input.clone());
+            continue;
+        }
+        for (first_line, content) in fenced_blocks(&input.source) {
+            split.push(LintInputFile {
+                source: format!("{}{content}", "\n".repeat(first_line)),
+                bom: false,
+                ..input.clone()
+            });
+        }
+    }
+    split
+}
+
+/// Gathers the per-block results of [`split_blocks`] into one result per
+/// input, in input order. A Markdown file without blocks gets an empty entry.
+pub fn merge_blocks(inputs: &[LintInputFile], results: Vec<LintFileResult>) -> Vec<LintFileResult> {
+    if !inputs.iter().any(|input| is_markdown(&input.file)) {
+        return results;
+    }
+    let mut merged: Vec<LintFileResult> = Vec::with_capacity(inputs.len());
+    for result in results {
+        match merged.iter_mut().find(|file| file.path == result.path) {
+            Some(file) => {
+                file.diagnostics.extend(result.diagnostics);
+                file.sources.extend(result.sources);
+            }
+            None => merged.push(result),
+        }
+    }
+    for input in inputs {
+        if is_markdown(&input.file) && !merged.iter().any(|file| file.path == input.path) {
+            merged.push(LintFileResult {
+                path: input.path.clone(),
+                diagnostics: Vec::new(),
+                sources: Vec::new(),
+            });
+        }
+    }
+    for file in &mut merged {
+        file.diagnostics.sort_by_key(|diagnostic| {
+            (
+                diagnostic.range.start.line,
+                diagnostic.range.start.character,
+            )
+        });
+    }
+    merged
+}
+
+/// Returns each tagged block's 0-based first content line and its content.
+/// Fences follow CommonMark: three or more backticks or tildes, indented at
+/// most three spaces, closed by a fence of the same character that is at
+/// least as long. An unclosed block runs to the end of the file.
+fn fenced_blocks(source: &str) -> Vec<(usize, String)> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(char, usize, bool, usize, String)> = None;
+    for (index, line) in source.lines().enumerate() {
+        let fence = parse_fence(line);
+        match &mut open {
+            Some((marker, length, tagged, first_line, content)) => {
+                let closes = fence.is_some_and(|(fence_marker, fence_length, info)| {
+                    fence_marker == *marker && fence_length >= *length && info.is_empty()
+                });
+                if closes {
+                    if *tagged {
+                        blocks.push((*first_line, std::mem::take(content)));
+                    }
+                    open = None;
+                } else if *tagged {
+                    content.push_str(line);
+                    content.push('\n');
+                }
+            }
+            None => {
+                if let Some((marker, length, info)) = fence {
+                    let language = info.split_whitespace().next().unwrap_or("");
+                    let tagged = BLOCK_LANGUAGES
+                        .iter()
+                        .any(|known| language.eq_ignore_ascii_case(known));
+                    open = Some((marker, length, tagged, index + 1, String::new()));
+                }
+            }
+        }
+    }
+    if let Some((_, _, true, first_line, content)) = open {
+        blocks.push((first_line, content));
+    }
+    blocks
+}
+
+/// Splits a fence line into its marker, length, and trimmed info string.
+fn parse_fence(line: &str) -> Option<(char, usize, &str)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let marker = trimmed
+        .chars()
+        .next()
+        .filter(|ch| *ch == '`' || *ch == '~')?;
+    let length = trimmed.chars().take_while(|ch| *ch == marker).count();
+    if length < 3 {
+        return None;
+    }
+    let info = trimmed[length..].trim();
+    if marker == '`' && info.contains('`') {
+        return None;
+    }
+    Some((marker, length, info))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fenced_blocks_keeps_only_tagged_blocks_with_their_lines() {
+        let source = "# Doc\n\n```juliet\nhalt;\n```\n\n```sh\nls\n```\n\n~~~~ JulietScript extra\ncreate A from juliet \"x\";\n```\n~~~~\n";
+        assert_eq!(
+            fenced_blocks(source),
+            vec![
+                (3, "halt;\n".to_owned()),
+                (11, "create A from juliet \"x\";\n```\n".to_owned()),
+            ]
+        );
+    }
+}
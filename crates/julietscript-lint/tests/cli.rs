@@ -1,4 +1,5 @@
 use std::fs;
+use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output, Stdio};
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -61,11 +62,16 @@ fn has_node() -> bool {
 }
 
 fn run_lint(root: &Path, globs: &[&str]) -> Output {
+    run_lint_with_args(root, globs, &[])
+}
+
+fn run_lint_with_args(root: &Path, globs: &[&str], args: &[&str]) -> Output {
     let mut command = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"));
     command.arg("--root").arg(root);
     for pattern in globs {
         command.arg("--glob").arg(pattern);
     }
+    command.args(args);
     command.output().expect("failed to run julietscript-lint")
 }
 
@@ -76,6 +82,24 @@ fn run_example() -> Output {
         .expect("failed to run julietscript-lint example")
 }
 
+fn run_new(path: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg("new")
+        .arg(path)
+        .args(args)
+        .output()
+        .expect("failed to run julietscript-lint new")
+}
+
+fn run_graph(path: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg("graph")
+        .arg(path)
+        .args(args)
+        .output()
+        .expect("failed to run julietscript-lint graph")
+}
+
 fn valid_script() -> &'static str {
     r#"juliet {
   engine = codex;
@@ -86,7 +110,7 @@ set "operator_email" as "email@test.com";
 policy triage = """Recover quickly.""";
 
 rubric quality {
-  criterion "Spec" points 1;
+  criterion "Spec" points 1 means "Matches the specification.";
 }
 
 cadence loop {
@@ -234,3 +258,2543 @@ fn exits_two_when_no_files_match() {
     let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
     assert!(stderr.contains("no files matched"));
 }
+
+#[test]
+fn fix_dry_run_prints_diff_without_writing() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let script = dir.file("scripts/missing-semicolon.julietscript");
+    let source = "juliet {\n  engine = codex;\n}\n\nhalt\n";
+    write_file(&script, source);
+    write_file(&dir.file("scripts/ok.julietscript"), valid_script());
+
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--fix-dry-run"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    let removed: Vec<&str> = stdout
+        .lines()
+        .filter(|line| line.starts_with("-") && !line.starts_with("---"))
+        .collect();
+    let added: Vec<&str> = stdout
+        .lines()
+        .filter(|line| line.starts_with("+") && !line.starts_with("+++"))
+        .collect();
+    assert_eq!(removed, vec!["-halt"]);
+    assert_eq!(added, vec!["+halt;"]);
+    assert!(stdout.contains("missing-semicolon.julietscript"));
+    assert!(!stdout.contains("ok.julietscript"));
+
+    let unchanged = fs::read_to_string(&script).expect("failed to read script");
+    assert_eq!(unchanged, source);
+}
+
+#[test]
+fn fix_writes_semicolons_and_reports_remaining_issues() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let script = dir.file("scripts/missing-semicolon.julietscript");
+    write_file(&script, "juliet {\n  engine = codex;\n}\n\nhalt\n");
+
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--fix"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let fixed = fs::read_to_string(&script).expect("failed to read script");
+    assert_eq!(fixed, "juliet {\n  engine = codex;\n}\n\nhalt;\n");
+}
+
+#[test]
+fn fix_only_applies_just_the_named_rule() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let script = dir.file("scripts/two-fixes.julietscript");
+    write_file(
+        &script,
+        "create Notes from julietArtifactSourceFiles [\"a.md\", \"a.md\"];\n\nhalt\n",
+    );
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--fix-only", "duplicate-source-path"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+
+    let fixed = fs::read_to_string(&script).expect("failed to read script");
+    assert_eq!(
+        fixed,
+        "create Notes from julietArtifactSourceFiles [\"a.md\"];\n\nhalt\n"
+    );
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(
+        stdout.contains("two-fixes.julietscript:4:1: error: Expected ';' after halt statement.")
+    );
+}
+
+#[test]
+fn strict_eof_fix_leaves_exactly_one_trailing_newline() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let missing = dir.file("scripts/missing.julietscript");
+    let extra = dir.file("scripts/extra.julietscript");
+    write_file(&missing, "halt;");
+    write_file(&extra, "halt;\n\n\n");
+
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--strict-eof"]);
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(
+        stdout.contains("missing.julietscript:1:6: warning: File does not end with a newline."),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("extra.julietscript:1:6: warning: File ends with more than one newline."),
+        "{stdout}"
+    );
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--strict-eof", "--fix"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(fs::read_to_string(&missing).unwrap(), "halt;\n");
+    assert_eq!(fs::read_to_string(&extra).unwrap(), "halt;\n");
+}
+
+#[test]
+fn fix_only_rejects_rules_without_autofix() {
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/ok.julietscript"), valid_script());
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--fix-only", "unknown-reference"],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(stderr.contains(
+        "rule 'unknown-reference' has no autofix. Fixable rules: missing-semicolon, duplicate-source-path, trailing-comma, quote-style"
+    ));
+}
+
+#[test]
+fn new_minimal_template_lints_cleanly() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let target = dir.file("scripts/starter.julietscript");
+    let output = run_new(&target, &["--template", "minimal"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let source = fs::read_to_string(&target).expect("template should be written");
+    assert!(source.contains("juliet {"));
+    assert!(source.contains("create FirstArtifact from juliet"));
+    assert!(source.contains("halt;"));
+
+    let lint_output = run_lint(dir.path(), &["**/*.julietscript"]);
+    assert_eq!(lint_output.status.code(), Some(0));
+
+    let stdout = String::from_utf8(lint_output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("Linted 1 file(s): 0 issue(s) (0 error(s), 0 warning(s))."));
+}
+
+#[test]
+fn new_refuses_to_overwrite_without_force() {
+    let dir = TestDir::new();
+    let target = dir.file("existing.julietscript");
+    write_file(&target, "halt;\n");
+
+    let output = run_new(&target, &["--template", "full"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(stderr.contains("already exists"));
+    assert_eq!(
+        fs::read_to_string(&target).expect("failed to read target"),
+        "halt;\n"
+    );
+
+    let output = run_new(&target, &["--template", "full", "--force"]);
+    assert_eq!(output.status.code(), Some(0));
+    let source = fs::read_to_string(&target).expect("failed to read target");
+    assert!(source.contains("# JulietScript specification example"));
+}
+
+#[test]
+fn json_format_reports_full_ranges() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/bad.julietscript"),
+        "policy triage = \"\"\"x\"\"\"\nhalt\n",
+    );
+
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--format", "json"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let results: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    let diagnostics = results["files"][0]["diagnostics"]
+        .as_array()
+        .expect("diagnostics should be an array");
+    assert_eq!(diagnostics.len(), 3);
+
+    for diagnostic in diagnostics {
+        let start = &diagnostic["range"]["start"];
+        let end = &diagnostic["range"]["end"];
+        let start_position = (start["line"].as_u64(), start["character"].as_u64());
+        let end_position = (end["line"].as_u64(), end["character"].as_u64());
+        assert!(end_position.0.is_some() && end_position.1.is_some());
+        assert!(end_position >= start_position);
+        assert!(end["offset"].as_u64() >= start["offset"].as_u64());
+    }
+}
+
+#[test]
+fn json_format_lists_clean_files_unless_only_failing() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/valid.julietscript"), valid_script());
+    write_file(
+        &dir.file("scripts/bad.julietscript"),
+        "policy triage = \"\"\"x\"\"\"\nhalt\n",
+    );
+
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--format", "json"]);
+    let report: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    let files = report["files"]
+        .as_array()
+        .expect("files should be an array");
+    assert_eq!(files.len(), 2);
+    let clean = files
+        .iter()
+        .find(|file| {
+            file["path"]
+                .as_str()
+                .unwrap()
+                .ends_with("valid.julietscript")
+        })
+        .expect("clean file should be listed");
+    assert_eq!(clean["diagnostics"], serde_json::json!([]));
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--format", "json", "--only-failing"],
+    );
+    let report: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    let files = report["files"]
+        .as_array()
+        .expect("files should be an array");
+    assert_eq!(files.len(), 1);
+    assert!(files[0]["path"]
+        .as_str()
+        .unwrap()
+        .ends_with("bad.julietscript"));
+    assert_eq!(report["summary"]["files"], 2);
+}
+
+#[test]
+fn codeclimate_format_reports_relative_paths_and_fingerprints() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/bad.julietscript"),
+        "policy triage = \"\"\"x\"\"\"\nhalt\n",
+    );
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--format", "codeclimate"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+
+    let issues: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    let issues = issues.as_array().expect("report should be an array");
+    assert_eq!(issues.len(), 3);
+
+    let mut fingerprints = Vec::new();
+    for issue in issues {
+        assert_eq!(issue["type"], "issue");
+        assert_eq!(issue["location"]["path"], "scripts/bad.julietscript");
+        assert!(issue["location"]["lines"]["begin"].as_u64() >= Some(1));
+        assert!(issue["check_name"].is_string());
+        assert!(["major", "minor"].contains(&issue["severity"].as_str().unwrap()));
+        fingerprints.push(issue["fingerprint"].as_str().unwrap().to_owned());
+    }
+    fingerprints.sort();
+    fingerprints.dedup();
+    assert_eq!(fingerprints.len(), 3, "fingerprints should be unique");
+}
+
+#[test]
+fn compact_format_prints_one_line_per_file_with_issues() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/bad.julietscript"),
+        "policy triage = \"\"\"x\"\"\"\nhalt\n",
+    );
+    write_file(&dir.file("scripts/ok.julietscript"), valid_script());
+
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--format", "compact"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0]
+        .ends_with("bad.julietscript: 3 issue(s) (first: Expected ';' after policy declaration.)"));
+    assert_eq!(
+        lines[1],
+        "Linted 2 file(s): 3 issue(s) (3 error(s), 0 warning(s))."
+    );
+}
+
+#[test]
+fn report_writes_json_results_alongside_text_output() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/bad.julietscript"),
+        "policy triage = \"\"\"x\"\"\"\nhalt\n",
+    );
+    let report = dir.file("reports/nested/out.json");
+    let report_arg = report.to_string_lossy().into_owned();
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--report", &report_arg],
+    );
+    assert_eq!(output.status.code(), Some(1));
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("Linted 1 file(s): 3 issue(s) (3 error(s), 0 warning(s))."));
+
+    let written = fs::read_to_string(&report).expect("report should be written");
+    let results: serde_json::Value = serde_json::from_str(&written).expect("report should be JSON");
+    let files = results["files"]
+        .as_array()
+        .expect("files should be an array");
+    assert_eq!(files.len(), 1);
+    assert!(files[0]["path"]
+        .as_str()
+        .expect("path should be a string")
+        .ends_with("bad.julietscript"));
+    assert_eq!(
+        files[0]["diagnostics"]
+            .as_array()
+            .expect("diagnostics should be an array")
+            .len(),
+        3
+    );
+}
+
+#[test]
+fn report_write_failure_exits_two() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/ok.julietscript"), valid_script());
+    write_file(&dir.file("blocker"), "not a directory");
+    let report_arg = dir.file("blocker/out.json").to_string_lossy().into_owned();
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--report", &report_arg],
+    );
+    assert_eq!(output.status.code(), Some(2));
+
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(stderr.contains("failed to create report directory"));
+}
+
+#[test]
+fn brace_patterns_match_multiple_extensions() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/one.juliet"), valid_script());
+    write_file(&dir.file("scripts/two.julietscript"), valid_script());
+    write_file(&dir.file("scripts/notes.md"), "not a script");
+
+    let output = run_lint(dir.path(), &["scripts/*.{juliet,julietscript}"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("Linted 2 file(s): 0 issue(s) (0 error(s), 0 warning(s))."));
+}
+
+#[test]
+fn no_default_linter_requires_explicit_linter() {
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/ok.julietscript"), valid_script());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg("--root")
+        .arg(dir.path())
+        .args(["--glob", "**/*.julietscript", "--no-default-linter"])
+        .env_remove("JULIETSCRIPT_LINTER_PATH")
+        .output()
+        .expect("failed to run julietscript-lint");
+    assert_eq!(output.status.code(), Some(2));
+
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(stderr.contains("--no-default-linter requires a linter"));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn print_linter_dumps_embedded_source() {
+    let output = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg("--print-linter")
+        .env_remove("JULIETSCRIPT_LINTER_PATH")
+        .output()
+        .expect("failed to run julietscript-lint --print-linter");
+    assert_eq!(output.status.code(), Some(0));
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(!stdout.is_empty());
+    assert!(stdout.contains("function lintJulietScript(source"));
+}
+
+#[test]
+fn print_linter_prefers_explicit_linter_file() {
+    let dir = TestDir::new();
+    let linter = dir.file("custom-linter.js");
+    write_file(
+        &linter,
+        "module.exports = { lintJulietScript: () => [] }; // custom-marker\n",
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg("--print-linter")
+        .arg("--linter")
+        .arg(&linter)
+        .output()
+        .expect("failed to run julietscript-lint --print-linter");
+    assert_eq!(output.status.code(), Some(0));
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("custom-marker"));
+}
+
+#[test]
+fn version_includes_embedded_linter_hash() {
+    let output = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg("--version")
+        .output()
+        .expect("failed to run julietscript-lint --version");
+    assert_eq!(output.status.code(), Some(0));
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.starts_with("julietscript-lint "));
+    assert!(stdout.contains("embedded linter fnv1a-64 "));
+}
+
+#[test]
+fn warn_unmatched_reports_scripts_outside_globs() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/linted.julietscript"), valid_script());
+    write_file(&dir.file("forgotten/skipped.julietscript"), valid_script());
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["scripts/*.julietscript"],
+        &["--warn-unmatched"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(
+        lines[0],
+        "Linted 1 file(s): 0 issue(s) (0 error(s), 0 warning(s))."
+    );
+    assert!(lines[1].starts_with("warning: "));
+    assert!(lines[1].contains("forgotten/skipped.julietscript was not matched"));
+    assert!(!stdout.contains("linted.julietscript was not matched"));
+}
+
+#[test]
+fn ext_drives_default_glob_when_no_glob_is_given() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/foo.juliet"), valid_script());
+    write_file(&dir.file("scripts/ignored.julietscript"), "not linted");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg("--root")
+        .arg(dir.path())
+        .args(["--ext", "juliet"])
+        .output()
+        .expect("failed to run julietscript-lint");
+    assert_eq!(output.status.code(), Some(0));
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("Linted 1 file(s): 0 issue(s) (0 error(s), 0 warning(s))."));
+}
+
+#[test]
+fn verbose_logs_patterns_and_runtime_to_stderr() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/ok.julietscript"), valid_script());
+
+    let output = run_lint_with_args(dir.path(), &["scripts/*.julietscript"], &["-v"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(stderr.contains("glob 'scripts/*.julietscript' resolved to '"));
+    assert!(stderr.contains("matched '"));
+    assert!(stderr.contains("runtime: node (embedded linter)"));
+    assert!(stderr.contains("bridge finished in "));
+    assert!(!stderr.contains("payload: "));
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert_eq!(
+        stdout,
+        "Linted 1 file(s): 0 issue(s) (0 error(s), 0 warning(s)).\n"
+    );
+
+    let output = run_lint_with_args(dir.path(), &["scripts/*.julietscript"], &["-vv"]);
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(stderr.contains("payload: "));
+}
+
+#[test]
+fn stats_prints_sorted_counts_per_rule() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/bad.julietscript"),
+        "policy triage = \"\"\"x\"\"\"\nhalt\n",
+    );
+    write_file(
+        &dir.file("scripts/dupes.julietscript"),
+        "set \"k\" as \"a\";\nset \"k\" as \"b\";\n",
+    );
+
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--stats"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    let stats: Vec<&str> = stdout
+        .lines()
+        .skip_while(|line| *line != "Issues by rule:")
+        .collect();
+    assert_eq!(
+        stats,
+        vec![
+            "Issues by rule:",
+            "  missing-semicolon: 2",
+            "  duplicate-global: 1",
+            "  syntax: 1",
+        ]
+    );
+}
+
+#[test]
+fn max_issues_per_file_truncates_output_but_not_counts() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/bad.julietscript"),
+        "set \"k\" as \"a\"\nset \"k\" as \"b\"\nset \"k\" as \"c\"\nhalt\n",
+    );
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--max-issues-per-file", "2"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].contains(": error: "));
+    assert!(lines[1].contains(": "));
+    assert!(lines[2].ends_with("bad.julietscript: ... and 5 more in this file."));
+    assert_eq!(
+        lines[3],
+        "Linted 1 file(s): 7 issue(s) (5 error(s), 2 warning(s))."
+    );
+}
+
+#[test]
+fn enable_and_disable_select_rules_by_id() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/tabs.julietscript"),
+        "cadence Loop {\n  variants = 1;\n\tsprints = 1;\n}\nhalt;\n",
+    );
+
+    let default_run = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--fail-level", "warning"],
+    );
+    assert_eq!(default_run.status.code(), Some(0));
+
+    let enabled = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--fail-level", "warning", "--enable", "indentation"],
+    );
+    assert_eq!(enabled.status.code(), Some(1));
+    let stdout = String::from_utf8(enabled.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("tabs.julietscript:3:1: warning: Block mixes tabs and spaces"));
+
+    let disabled = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &[
+            "--fail-level",
+            "warning",
+            "--enable",
+            "indentation",
+            "--disable",
+            "indentation",
+        ],
+    );
+    assert_eq!(disabled.status.code(), Some(0));
+}
+
+#[test]
+fn graph_prints_artifact_dependencies_from_example_script() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let script = dir.file("example.julietscript");
+    write_file(&script, &String::from_utf8(run_example().stdout).unwrap());
+
+    let output = run_graph(&script, &[]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.starts_with("digraph artifacts {\n"));
+    assert!(stdout.contains("  SourceBrief -> IterationPlan;\n"));
+    assert!(stdout.trim_end().ends_with('}'));
+
+    let output = run_graph(&script, &["--format", "json"]);
+    assert_eq!(output.status.code(), Some(0));
+    let graph: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("graph should be valid JSON");
+    let dependents = graph["SourceBrief"]
+        .as_array()
+        .expect("SourceBrief should have dependents");
+    assert!(dependents.contains(&serde_json::json!("IterationPlan")));
+}
+
+#[test]
+fn graph_refuses_scripts_with_unresolved_references() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let script = dir.file("broken.julietscript");
+    write_file(
+        &script,
+        "create Plan from juliet \"Plan it.\" using [Missing];\n",
+    );
+
+    let output = run_graph(&script, &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(stderr.contains("Unknown artifact 'Missing' in using list."));
+}
+
+#[test]
+fn fail_level_controls_which_severities_fail_the_run() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/warn.julietscript"),
+        "set \"k\" as \"a\";\nset \"k\" as \"b\";\n",
+    );
+    let globs = &["**/*.julietscript"];
+
+    let default_run = run_lint(dir.path(), globs);
+    assert_eq!(default_run.status.code(), Some(0));
+    let stdout = String::from_utf8(default_run.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("(0 error(s), 1 warning(s))"));
+
+    let error_level = run_lint_with_args(dir.path(), globs, &["--fail-level", "error"]);
+    assert_eq!(error_level.status.code(), Some(0));
+
+    let warning_level = run_lint_with_args(dir.path(), globs, &["--fail-level", "warning"]);
+    assert_eq!(warning_level.status.code(), Some(1));
+
+    write_file(&dir.file("scripts/broken.julietscript"), "halt\n");
+    let error_run = run_lint(dir.path(), globs);
+    assert_eq!(error_run.status.code(), Some(1));
+
+    let never = run_lint_with_args(dir.path(), globs, &["--fail-level", "never"]);
+    assert_eq!(never.status.code(), Some(0));
+}
+
+#[test]
+fn stream_prints_finished_files_before_later_files_complete() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let release = dir.file("release");
+    // The slow file blocks until the test sees the fast file's output, so this
+    // only passes if that output is printed while the bridge is still running.
+    write_file(
+        &dir.file("slow-linter.js"),
+        r#"const fs = require("fs");
+function lintJulietScript(source) {
+  const waitFor = source.match(/^# wait-for: (.*)$/m);
+  let message = "fast";
+  if (waitFor) {
+    const deadline = Date.now() + 10000;
+    while (!fs.existsSync(waitFor[1]) && Date.now() < deadline) {}
+    message = fs.existsSync(waitFor[1]) ? "released" : "timed out";
+  }
+  const position = { line: 0, character: 0 };
+  return [{ severity: "warning", message, range: { start: position, end: position } }];
+}
+module.exports = { lintJulietScript };
+"#,
+    );
+    write_file(&dir.file("scripts/a.julietscript"), "halt;\n");
+    write_file(
+        &dir.file("scripts/b.julietscript"),
+        &format!("# wait-for: {}\nhalt;\n", release.display()),
+    );
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg("--root")
+        .arg(dir.path())
+        .args(["--glob", "scripts/*.julietscript", "--stream", "--linter"])
+        .arg(dir.file("slow-linter.js"))
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run julietscript-lint");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout should be piped"));
+
+    let mut first = String::new();
+    stdout
+        .read_line(&mut first)
+        .expect("should read first line");
+    assert!(
+        first.ends_with("a.julietscript:1:1: warning: fast.\n"),
+        "{first}"
+    );
+    write_file(&release, "");
+
+    let rest: Vec<String> = stdout.lines().map(|line| line.unwrap()).collect();
+    assert!(child.wait().expect("lint should finish").success());
+    assert!(
+        rest[0].ends_with("b.julietscript:1:1: warning: released."),
+        "{rest:?}"
+    );
+    assert_eq!(
+        rest[1],
+        "Linted 2 file(s): 2 issue(s) (0 error(s), 2 warning(s))."
+    );
+}
+
+#[test]
+fn input_list_lints_exactly_the_listed_files() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/a.julietscript"), valid_script());
+    write_file(&dir.file("scripts/b.julietscript"), "halt\n");
+    write_file(&dir.file("scripts/unlisted.julietscript"), "halt\n");
+    write_file(
+        &dir.file("changed.txt"),
+        &format!(
+            "scripts/a.julietscript\n\n{}\n",
+            dir.file("scripts/b.julietscript").display()
+        ),
+    );
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &[],
+        &["--input-list", dir.file("changed.txt").to_str().unwrap()],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("b.julietscript:2:1: error:"));
+    assert!(!stdout.contains("unlisted.julietscript"));
+    assert!(stdout.contains("Linted 2 file(s):"));
+}
+
+#[test]
+fn input_list_reports_each_missing_path() {
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/a.julietscript"), valid_script());
+    write_file(
+        &dir.file("changed.txt"),
+        "scripts/a.julietscript\nscripts/gone.julietscript\nscripts/also-gone.julietscript\n",
+    );
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &[],
+        &["--input-list", dir.file("changed.txt").to_str().unwrap()],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(stderr.contains("  scripts/gone.julietscript: no such file"));
+    assert!(stderr.contains("  scripts/also-gone.julietscript: no such file"));
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--input-list", dir.file("changed.txt").to_str().unwrap()],
+    );
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn response_file_arguments_are_expanded() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/bad.julietscript"), "halt\n");
+    write_file(&dir.file("args.txt"), "--glob '**/*.julietscript'\n");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg("--root")
+        .arg(dir.path())
+        .arg(format!("@{}", dir.file("args.txt").display()))
+        .output()
+        .expect("failed to run julietscript-lint");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("bad.julietscript:2:1: error:"));
+
+    write_file(&dir.file("nested.txt"), "@args.txt\n");
+    let output = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg(format!("@{}", dir.file("nested.txt").display()))
+        .output()
+        .expect("failed to run julietscript-lint");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(stderr.contains("nested response files are not supported"));
+}
+
+#[test]
+fn output_writes_report_to_file_instead_of_stdout() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/bad.julietscript"), "halt\n");
+    let report = dir.file("out/nested/report.txt");
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--output", report.to_str().unwrap(), "--format", "compact"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+
+    let written = fs::read_to_string(&report).expect("report should be written");
+    assert!(written.contains("bad.julietscript: 2 issue(s)"));
+    assert!(written.ends_with("Linted 1 file(s): 2 issue(s) (2 error(s), 0 warning(s)).\n"));
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--output", dir.file("out").to_str().unwrap()],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(stderr.contains("failed to create --output file"));
+}
+
+#[test]
+fn max_line_length_flag_enables_the_rule_with_a_limit() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/long.julietscript"),
+        &format!("# {}\nhalt;\n", "x".repeat(60)),
+    );
+
+    let default_run = run_lint(dir.path(), &["**/*.julietscript"]);
+    let stdout = String::from_utf8(default_run.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("0 issue(s)"));
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--max-line-length", "50"],
+    );
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout
+        .contains("long.julietscript:1:51: warning: Line is 62 characters long; the limit is 50."));
+}
+
+#[test]
+fn max_line_length_of_zero_is_rejected() {
+    let dir = TestDir::new();
+    let output = run_lint_with_args(dir.path(), &[], &["--max-line-length", "0"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(stderr.contains("--max-line-length"), "{stderr}");
+
+    write_file(
+        &dir.file("julietscript-lint.json"),
+        r#"{ "max-line-length": 0 }"#,
+    );
+    let output = run_lint_with_args(
+        dir.path(),
+        &[],
+        &[
+            "--config",
+            dir.file("julietscript-lint.json").to_str().unwrap(),
+            "--print-config",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(stderr.contains("nonzero"), "{stderr}");
+}
+
+#[test]
+fn baseline_diff_counts_new_and_resolved_issues() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let script = dir.file("scripts/legacy.julietscript");
+    write_file(&script, "set \"k\" as \"a\";\nset \"k\" as \"b\";\n");
+    let baseline = dir.file("baseline.json");
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--report", baseline.to_str().unwrap()],
+    );
+    assert_eq!(output.status.code(), Some(0));
+
+    // Shift the known warning down a line and add one new issue.
+    write_file(
+        &script,
+        "\nset \"k\" as \"a\";\nset \"k\" as \"b\";\nset \"k\" as \"c\";\n",
+    );
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--baseline", baseline.to_str().unwrap(), "--baseline-diff"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "new=1 resolved=0");
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1]
+        .ends_with("legacy.julietscript:4:5: warning: Duplicate global key assignment 'k'."));
+
+    write_file(&script, "set \"k\" as \"a\";\n");
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--baseline", baseline.to_str().unwrap(), "--baseline-diff"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.starts_with("new=0 resolved=1\n"));
+    assert!(stdout.contains("resolved: "));
+    assert!(stdout.contains("legacy.julietscript: Duplicate global key assignment 'k'."));
+}
+
+#[test]
+fn print_config_merges_file_and_flags_with_flags_winning() {
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("julietscript-lint.json"),
+        r#"{ "max-line-length": 80, "disable": ["duplicate-global"], "excludes": ["vendor/**"] }"#,
+    );
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &[],
+        &[
+            "--config",
+            dir.file("julietscript-lint.json").to_str().unwrap(),
+            "--max-line-length",
+            "100",
+            "--fail-level",
+            "warning",
+            "--print-config",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let config: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    assert_eq!(config["max-line-length"], 100);
+    assert_eq!(config["fail-level"], "warning");
+    assert_eq!(config["disable"], serde_json::json!(["duplicate-global"]));
+    assert_eq!(config["excludes"], serde_json::json!(["vendor/**"]));
+}
+
+#[test]
+fn linter_crash_on_one_file_is_reported_and_other_files_still_lint() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("crashy-linter.js"),
+        r#"function lintJulietScript(source) {
+  if (source.includes("boom")) {
+    throw new Error("cannot handle boom");
+  }
+  const position = { line: 0, character: 0 };
+  return [{ severity: "warning", message: "looked", range: { start: position, end: position } }];
+}
+module.exports = { lintJulietScript };
+"#,
+    );
+    write_file(&dir.file("scripts/a.julietscript"), "halt;\n");
+    write_file(&dir.file("scripts/b.julietscript"), "# boom\nhalt;\n");
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["scripts/*.julietscript"],
+        &["--linter", dir.file("crashy-linter.js").to_str().unwrap()],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("a.julietscript:1:1: warning: looked"));
+    assert!(stdout.contains("b.julietscript:1:1: error: Linter crashed: cannot handle boom"));
+}
+
+#[test]
+fn quiet_clean_prints_nothing_for_clean_runs_only() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/good.julietscript"), valid_script());
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--quiet-clean"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+
+    write_file(&dir.file("scripts/bad.julietscript"), "halt\n");
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--quiet-clean"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("bad.julietscript:2:1: error:"));
+    assert!(stdout.contains("Linted 2 file(s):"));
+}
+
+#[test]
+fn quiet_clean_still_reports_files_that_were_not_linted() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/good.julietscript"), valid_script());
+    write_file(
+        &dir.file("scripts/huge.julietscript"),
+        &format!("# {}\nhalt;\n", "x".repeat(3000)),
+    );
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--quiet-clean", "--max-file-size", "100"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(
+        stdout.contains("huge.julietscript is 3009 bytes, over the --max-file-size limit of 100, and was not linted."),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("Linted"), "{stdout}");
+}
+
+#[test]
+fn no_summary_drops_the_summary_line_but_keeps_diagnostics() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/bad.julietscript"), "halt\n");
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--no-summary"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("bad.julietscript:2:1: error:"));
+    assert!(!stdout.contains("Linted "), "{stdout}");
+}
+
+#[test]
+fn relative_to_renders_paths_independent_of_root() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("repo/app/flows/bad.julietscript"), "halt\n");
+    let repo = dir.file("repo");
+    let relative_to = repo.to_str().expect("path should be utf8");
+    let output = run_lint_with_args(
+        &dir.file("repo/app"),
+        &["**/*.julietscript"],
+        &["--relative-to", relative_to],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(
+        stdout.starts_with("app/flows/bad.julietscript:2:1: error:"),
+        "{stdout}"
+    );
+
+    // Without --relative-to, paths are relative to --root in every format.
+    let output = run_lint(&dir.file("repo/app"), &["**/*.julietscript"]);
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(
+        stdout.starts_with("flows/bad.julietscript:2:1: error:"),
+        "{stdout}"
+    );
+    let output = run_lint_with_args(
+        &dir.file("repo/app"),
+        &["**/*.julietscript"],
+        &["--format", "codeclimate"],
+    );
+    let issues: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    assert_eq!(
+        issues[0]["location"]["path"], "flows/bad.julietscript",
+        "{issues}"
+    );
+}
+
+#[test]
+fn fixable_only_shows_just_diagnostics_with_an_autofix() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/mixed.julietscript"),
+        "create Brief from julietArtifactSourceFiles [\"./a.md\",];\nhalt \"\";\n",
+    );
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--fixable-only"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("Trailing comma is not allowed"), "{stdout}");
+    assert!(
+        !stdout.contains("Halt message should not be empty"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("Linted 1 file(s): 2 issue(s) (1 error(s), 1 warning(s))."));
+    assert!(stdout.contains("Hid 1 issue(s) without an autofix (--fixable-only)."));
+}
+
+#[test]
+fn error_on_elevates_only_the_named_rule() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/warn.julietscript"),
+        "set \"k\" as \"a\";\nset \"k\" as \"b\";\nhalt \"\";\n",
+    );
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &[]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--error-on", "empty-halt-message"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("warn.julietscript:2:5: warning: Duplicate global key assignment 'k'."));
+    assert!(stdout.contains("warn.julietscript:3:6: error: Halt message should not be empty"));
+    assert!(stdout.contains("(1 error(s), 1 warning(s))"), "{stdout}");
+}
+
+#[test]
+fn schema_subcommand_describes_the_json_report() {
+    let output = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg("schema")
+        .output()
+        .expect("failed to run julietscript-lint schema");
+    assert_eq!(output.status.code(), Some(0));
+    let schema: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("schema should be valid JSON");
+    let defs = &schema["$defs"];
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["properties"]["files"]["type"], "array");
+    assert_eq!(
+        defs["fileResult"]["properties"]["diagnostics"]["type"],
+        "array"
+    );
+
+    if !has_node() {
+        eprintln!("Skipping report check: node is not available.");
+        return;
+    }
+    // Every key the report emits must be declared, so the schema cannot drift
+    // from the serialized types unnoticed.
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/bad.julietscript"), "halt\n");
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--format", "json"]);
+    let report: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("report should be valid JSON");
+    let declared = |def: &str, value: &serde_json::Value| {
+        let properties = defs[def]["properties"]
+            .as_object()
+            .expect("definition should list properties");
+        for key in value
+            .as_object()
+            .expect("report entry should be an object")
+            .keys()
+        {
+            assert!(
+                properties.contains_key(key),
+                "{def} does not declare '{key}'"
+            );
+        }
+    };
+    for key in report
+        .as_object()
+        .expect("report should be an object")
+        .keys()
+    {
+        assert!(
+            schema["properties"].get(key).is_some(),
+            "report declares no '{key}'"
+        );
+    }
+    declared("summary", &report["summary"]);
+    for file in report["files"]
+        .as_array()
+        .expect("files should be an array")
+    {
+        declared("fileResult", file);
+        for diagnostic in file["diagnostics"].as_array().expect("diagnostics array") {
+            declared("diagnostic", diagnostic);
+            declared("range", &diagnostic["range"]);
+            declared("position", &diagnostic["range"]["start"]);
+            declared("position", &diagnostic["range"]["end"]);
+        }
+    }
+}
+
+#[test]
+fn eol_controls_report_line_endings() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/bad.julietscript"), "halt\n");
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &[]);
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(
+        stdout.ends_with(").\n") && !stdout.contains('\r'),
+        "{stdout:?}"
+    );
+
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--eol", "crlf"]);
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.ends_with(").\r\n"), "{stdout:?}");
+    assert_eq!(stdout.matches('\n').count(), stdout.matches("\r\n").count());
+}
+
+#[test]
+fn require_halt_flag_enables_the_rule() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/open.julietscript"),
+        "create Brief from juliet \"Summarize the repo.\";\n",
+    );
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &[]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--require-halt"]);
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(
+        stdout.contains("open.julietscript:2:1: warning: Script has no 'halt' statement"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn count_prints_matched_files_without_linting() {
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/a.julietscript"), "halt\n");
+    write_file(&dir.file("scripts/b.julietscript"), "halt;\n");
+    write_file(&dir.file("scripts/nested/c.julietscript"), "halt;\n");
+    write_file(&dir.file("scripts/notes.md"), "not a script\n");
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--count"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "3\n");
+
+    let output = run_lint_with_args(dir.path(), &["**/*.jls"], &["--count"]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = run_lint_with_args(dir.path(), &["**/*.jls"], &["--count", "--allow-empty"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "0\n");
+}
+
+#[test]
+fn max_file_size_skips_oversized_files_with_a_notice() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/good.julietscript"), valid_script());
+    write_file(
+        &dir.file("scripts/huge.julietscript"),
+        &"halt\n".repeat(1024),
+    );
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--max-file-size", "2048"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("Linted 1 file(s): 0 issue(s)"), "{stdout}");
+    assert!(
+        stdout.contains("huge.julietscript is 5120 bytes, over the --max-file-size limit of 2048, and was not linted."),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn check_sources_resolves_paths_against_each_script_directory() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("flows/one/brief.md"), "# Brief\n");
+    write_file(
+        &dir.file("flows/one/one.julietscript"),
+        "create Brief from julietArtifactSourceFiles [\"./brief.md\"];\nhalt;\n",
+    );
+    write_file(&dir.file("flows/two/notes.md"), "# Notes\n");
+    write_file(
+        &dir.file("flows/two/two.julietscript"),
+        "create Notes from julietArtifactSourceFiles [\"notes.md\", \"./brief.md\"];\nhalt;\n",
+    );
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--check-sources"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(
+        stdout.contains("two.julietscript:1:58: error: Source file './brief.md' does not exist relative to this script's directory."),
+        "{stdout}"
+    );
+    assert!(stdout.contains("Linted 2 file(s): 1 issue(s)"), "{stdout}");
+}
+
+#[test]
+fn fail_on_fails_the_run_for_a_warning_rule_without_changing_severity() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/warn.julietscript"),
+        "set \"k\" as \"a\";\nset \"k\" as \"b\";\nhalt;\n",
+    );
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &[]);
+    assert_eq!(output.status.code(), Some(0));
+
+    for extra in [&[][..], &["--fail-level", "never"][..]] {
+        let mut args = vec!["--fail-on", "duplicate-global"];
+        args.extend_from_slice(extra);
+        let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &args);
+        assert_eq!(output.status.code(), Some(1));
+        let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+        assert!(
+            stdout.contains("warn.julietscript:2:5: warning: Duplicate global key assignment 'k'.")
+        );
+    }
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--fail-on", "empty-policy"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn json_output_is_versioned_with_a_legacy_array_fallback() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/bad.julietscript"), "halt\n");
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--format", "json"]);
+    let report: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    assert_eq!(report["version"], 1);
+    assert_eq!(report["files"].as_array().expect("files array").len(), 1);
+    assert_eq!(report["summary"]["files"], 1);
+    assert_eq!(report["summary"]["errors"], 2);
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--format", "json", "--json-legacy"],
+    );
+    let legacy: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    assert_eq!(legacy, report["files"]);
+}
+
+#[test]
+fn print_ast_writes_the_parse_tree_without_running_rules() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/valid.julietscript"), valid_script());
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--print-ast"]);
+    assert_eq!(output.status.code(), Some(0));
+    let trees: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("parse trees should be valid JSON");
+    let ast = &trees[0]["ast"];
+    assert_eq!(ast["type"], "script");
+    let body = ast["body"].as_array().expect("script should have a body");
+    let juliet = body
+        .iter()
+        .find(|node| node["type"] == "juliet")
+        .expect("parse tree should contain the juliet block");
+    assert_eq!(juliet["range"]["start"]["line"], 0);
+}
+
+#[test]
+fn stdin_fix_writes_the_fixed_source_to_stdout() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg("--root")
+        .arg(dir.path())
+        .args(["--stdin", "--fix"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run julietscript-lint");
+    child
+        .stdin
+        .take()
+        .expect("stdin should be piped")
+        .write_all(b"juliet {\n  engine = codex;\n}\n\nhalt \"\"\n")
+        .expect("failed to write stdin");
+    let output = child.wait_with_output().expect("lint should finish");
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert_eq!(output.status.code(), Some(0), "{stderr}");
+    assert_eq!(stdout, "juliet {\n  engine = codex;\n}\n\nhalt \"\";\n");
+    assert!(!stderr.contains("Expected ';'"), "{stderr}");
+    assert!(
+        stderr.contains("Halt message should not be empty"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn bridge_node_args_are_passed_to_node_but_cannot_replace_the_bridge() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/valid.julietscript"), valid_script());
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--bridge-node-args", "--no-warnings"],
+    );
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert_eq!(output.status.code(), Some(0), "{stdout}");
+    assert!(stdout.contains("Linted 1 file(s)"), "{stdout}");
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--bridge-node-args", "--no-warnings --eval=process.exit(0)"],
+    );
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr.contains("cannot include '--eval'"), "{stderr}");
+
+    // An env file can set NODE_OPTIONS, and --check never runs the bridge.
+    write_file(
+        &dir.file("envf"),
+        &format!(
+            "NODE_OPTIONS=\"--require {}\"\n",
+            dir.file("evil.js").display()
+        ),
+    );
+    let env_file = format!("--env-file={}", dir.file("envf").display());
+    for flags in [env_file.as_str(), "--check"] {
+        let output = run_lint_with_args(
+            dir.path(),
+            &["**/*.julietscript"],
+            &["--bridge-node-args", flags],
+        );
+        let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+        assert_eq!(output.status.code(), Some(2), "{flags}: {stderr}");
+        assert!(stderr.contains("cannot include"), "{flags}: {stderr}");
+    }
+}
+
+#[test]
+fn dump_payload_writes_the_files_sent_to_the_bridge() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/valid.julietscript"), valid_script());
+    let dump = dir.file("payload.json");
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--dump-payload", dump.to_str().unwrap()],
+    );
+    assert_eq!(output.status.code(), Some(0));
+
+    let payload: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&dump).expect("payload should be written"))
+            .expect("payload should be JSON");
+    let files = payload.as_array().expect("payload should be an array");
+    assert_eq!(files.len(), 1);
+    assert!(files[0]["path"]
+        .as_str()
+        .unwrap()
+        .ends_with("valid.julietscript"));
+    assert_eq!(files[0]["source"], valid_script());
+
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--dump-payload", "-"]);
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    let payload: serde_json::Value =
+        serde_json::from_str(stderr.trim()).expect("stderr should hold the payload");
+    assert_eq!(payload[0]["source"], valid_script());
+}
+
+#[test]
+fn group_by_rule_prints_a_section_per_rule() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/a.julietscript"), "halt \"\";\n");
+    write_file(
+        &dir.file("scripts/b.julietscript"),
+        "create Memo from juliet \"Draft.\" with { };\nhalt \"\";\n",
+    );
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--group-by-rule"]);
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert_eq!(output.status.code(), Some(0), "{stdout}");
+
+    let halt = stdout
+        .find("empty-halt-message (2 issue(s)):\n")
+        .expect("empty-halt-message section");
+    let attachments = stdout
+        .find("empty-attachments (1 issue(s)):\n")
+        .expect("empty-attachments section");
+    assert!(halt < attachments, "{stdout}");
+    let halt_section = &stdout[halt..attachments];
+    assert!(
+        halt_section.contains("a.julietscript:1:6: warning: Halt message"),
+        "{stdout}"
+    );
+    assert!(
+        halt_section.contains("b.julietscript:2:6: warning: Halt message"),
+        "{stdout}"
+    );
+    assert!(
+        stdout[attachments..].contains("b.julietscript:1:34: warning: Artifact 'Memo'"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("Issues by rule:\n  empty-halt-message: 2\n  empty-attachments: 1\n"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.ends_with("Linted 2 file(s): 3 issue(s) (0 error(s), 3 warning(s)).\n"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn exit_zero_passes_the_run_but_still_prints_diagnostics() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/bad.julietscript"), "halt\n");
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--exit-zero", "--fail-on", "missing-semicolon"],
+    );
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert_eq!(output.status.code(), Some(0), "{stdout}");
+    assert!(
+        stdout.contains("error: Expected ';' after halt statement."),
+        "{stdout}"
+    );
+
+    let output = run_lint_with_args(dir.path(), &["**/*.missing"], &["--exit-zero"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn diagnostic_messages_end_with_a_single_period() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("terse-linter.js"),
+        r#"function lintJulietScript() {
+  const position = { line: 0, character: 0 };
+  const range = { start: position, end: position };
+  return ["Missing period  ", "Too many periods..", "Is this intended?"].map((message) => ({ severity: "warning", message, range }));
+}
+module.exports = { lintJulietScript };
+"#,
+    );
+    write_file(&dir.file("scripts/a.julietscript"), "halt;\n");
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["scripts/*.julietscript"],
+        &["--linter", dir.file("terse-linter.js").to_str().unwrap()],
+    );
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("warning: Missing period.\n"), "{stdout}");
+    assert!(stdout.contains("warning: Too many periods.\n"), "{stdout}");
+    assert!(stdout.contains("warning: Is this intended?\n"), "{stdout}");
+}
+
+#[test]
+fn repeated_linter_flags_merge_diagnostics() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let linter = |messages: &str| {
+        format!(
+            r#"function lintJulietScript() {{
+  const position = {{ line: 0, character: 0 }};
+  const range = {{ start: position, end: position }};
+  return [{messages}].map((message) => ({{ severity: "warning", rule: "custom", message, range }}));
+}}
+module.exports = {{ lintJulietScript }};
+"#
+        )
+    };
+    write_file(&dir.file("lenient.js"), &linter(r#""Shared warning.""#));
+    write_file(
+        &dir.file("strict.js"),
+        &linter(r#""Shared warning.", "Strict warning.""#),
+    );
+    write_file(&dir.file("scripts/a.julietscript"), "halt;\n");
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["scripts/*.julietscript"],
+        &[
+            "--linter",
+            dir.file("lenient.js").to_str().unwrap(),
+            "--linter",
+            dir.file("strict.js").to_str().unwrap(),
+            "--verbose",
+        ],
+    );
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert_eq!(
+        stdout.matches("warning: Shared warning.").count(),
+        1,
+        "{stdout}"
+    );
+    assert_eq!(
+        stdout.matches("warning: Strict warning.").count(),
+        1,
+        "{stdout}"
+    );
+    assert!(stdout.contains("2 warning(s)"), "{stdout}");
+    assert!(
+        stderr.contains("a.julietscript:1:1: Strict warning.") && stderr.contains("strict.js': "),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn only_errors_hides_warnings_but_keeps_them_in_the_summary() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/mixed.julietscript"),
+        "halt \"\";\nhalt\n",
+    );
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--only-errors"]);
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert_eq!(output.status.code(), Some(1), "{stdout}");
+    assert!(
+        stdout.contains("mixed.julietscript:3:1: error: Expected ';' after halt statement."),
+        "{stdout}"
+    );
+    assert!(!stdout.contains(": warning:"), "{stdout}");
+    assert!(stdout.contains("(2 error(s), 1 warning(s))"), "{stdout}");
+    assert!(
+        stdout.contains("Hid 1 warning(s) (--only-errors)."),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn bridge_loads_the_linter_once_for_a_multi_file_payload() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let loads = dir.file("loads");
+    write_file(
+        &dir.file("counting-linter.js"),
+        &format!(
+            r#"require("fs").appendFileSync({loads:?}, "loaded\n");
+function lintJulietScript() {{
+  return [];
+}}
+module.exports = {{ lintJulietScript }};
+"#,
+            loads = loads.display().to_string()
+        ),
+    );
+    for name in ["a", "b", "c"] {
+        write_file(
+            &dir.file(&format!("scripts/{name}.julietscript")),
+            "halt;\n",
+        );
+    }
+
+    let linter = dir.file("counting-linter.js");
+    for extra in [&[][..], &["--stream"][..]] {
+        write_file(&loads, "");
+        let mut args = vec!["--linter", linter.to_str().unwrap()];
+        args.extend_from_slice(extra);
+        let output = run_lint_with_args(dir.path(), &["scripts/*.julietscript"], &args);
+        let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+        assert_eq!(output.status.code(), Some(0), "{stdout}");
+        assert!(stdout.contains("Linted 3 file(s)"), "{stdout}");
+        assert_eq!(
+            fs::read_to_string(&loads).expect("loads should be written"),
+            "loaded\n",
+            "{extra:?}"
+        );
+    }
+}
+
+#[test]
+fn cache_drops_entries_for_files_no_longer_linted() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/a.julietscript"), valid_script());
+    write_file(&dir.file("scripts/b.julietscript"), valid_script());
+    let cached_files = || -> Vec<String> {
+        let text = fs::read_to_string(dir.file(".julietlint-cache.json"))
+            .expect("cache should be written");
+        let cache: serde_json::Value = serde_json::from_str(&text).expect("cache should be JSON");
+        cache["files"]
+            .as_object()
+            .expect("cache should have files")
+            .keys()
+            .cloned()
+            .collect()
+    };
+
+    let output = run_lint_with_args(dir.path(), &["scripts/*.julietscript"], &["--cache"]);
+    assert_eq!(output.status.code(), Some(0));
+    let files = cached_files();
+    assert_eq!(files.len(), 2, "{files:?}");
+
+    // A run over a subset keeps the other files' entries.
+    let output = run_lint_with_args(dir.path(), &["scripts/a.julietscript"], &["--cache"]);
+    assert_eq!(output.status.code(), Some(0));
+    let files = cached_files();
+    assert_eq!(files.len(), 2, "{files:?}");
+
+    fs::remove_file(dir.file("scripts/b.julietscript")).expect("failed to remove b");
+    let output = run_lint_with_args(dir.path(), &["scripts/*.julietscript"], &["--cache"]);
+    assert_eq!(output.status.code(), Some(0));
+    let files = cached_files();
+    assert_eq!(files.len(), 1, "{files:?}");
+    assert!(files[0].ends_with("a.julietscript"), "{files:?}");
+}
+
+#[test]
+fn cache_dir_holds_the_cache_and_unchanged_files_skip_the_linter() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let loads = dir.file("loads");
+    write_file(
+        &dir.file("counting-linter.js"),
+        &format!(
+            r#"require("fs").appendFileSync({loads:?}, "loaded\n");
+function lintJulietScript() {{
+  const position = {{ line: 0, character: 0 }};
+  return [{{ severity: "warning", message: "Looked.", range: {{ start: position, end: position }} }}];
+}}
+module.exports = {{ lintJulietScript }};
+"#,
+            loads = loads.display().to_string()
+        ),
+    );
+    write_file(&dir.file("scripts/a.julietscript"), "halt;\n");
+    let linter = dir.file("counting-linter.js");
+    let cache_dir = dir.file("ci-cache/julietlint");
+    let args = [
+        "--linter",
+        linter.to_str().unwrap(),
+        "--cache",
+        "--cache-dir",
+        cache_dir.to_str().unwrap(),
+    ];
+
+    let first = run_lint_with_args(dir.path(), &["scripts/*.julietscript"], &args);
+    assert_eq!(first.status.code(), Some(0));
+    assert!(cache_dir.join(".julietlint-cache.json").is_file());
+    assert!(!dir.file(".julietlint-cache.json").exists());
+
+    let second = run_lint_with_args(dir.path(), &["scripts/*.julietscript"], &args);
+    assert_eq!(second.status.code(), Some(0));
+    assert_eq!(first.stdout, second.stdout);
+    assert_eq!(
+        fs::read_to_string(&loads).expect("loads should be written"),
+        "loaded\n"
+    );
+
+    // A cache directory that cannot be created only costs the cache.
+    write_file(&dir.file("not-a-dir"), "");
+    let output = run_lint_with_args(
+        dir.path(),
+        &["scripts/*.julietscript"],
+        &[
+            "--cache",
+            "--cache-dir",
+            dir.file("not-a-dir/cache").to_str().unwrap(),
+        ],
+    );
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert_eq!(output.status.code(), Some(0), "{stderr}");
+    assert!(
+        stderr.contains("warning: could not write cache"),
+        "{stderr}"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn no_canonicalize_keeps_symlinked_script_paths() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("real/bad.julietscript"),
+        "policy triage = \"\"\"x\"\"\"\nhalt\n",
+    );
+    std::os::unix::fs::symlink(dir.file("real"), dir.file("link")).expect("failed to symlink");
+
+    let output = run_lint_with_args(dir.path(), &["link/*.julietscript"], &[]);
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("real/bad.julietscript:"), "{stdout}");
+
+    let output = run_lint_with_args(dir.path(), &["link/*.julietscript"], &["--no-canonicalize"]);
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("link/bad.julietscript:"), "{stdout}");
+    assert!(!stdout.contains("real/"), "{stdout}");
+}
+
+#[test]
+fn highlight_worst_names_the_file_with_the_most_errors() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/a.julietscript"),
+        "policy a = \"\"\"x\"\"\"\n",
+    );
+    write_file(
+        &dir.file("scripts/b.julietscript"),
+        "policy a = \"\"\"x\"\"\"\npolicy b = \"\"\"y\"\"\"\n",
+    );
+    write_file(
+        &dir.file("scripts/c.julietscript"),
+        "policy a = \"\"\"x\"\"\"\npolicy b = \"\"\"y\"\"\"\n",
+    );
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["scripts/*.julietscript"],
+        &[
+            "--highlight-worst",
+            "--relative-to",
+            dir.path().to_str().unwrap(),
+        ],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(
+        stdout.ends_with(
+            "(5 error(s), 0 warning(s)).\nMost issues: scripts/b.julietscript (2 error(s), 0 warning(s)).\n"
+        ),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn markdown_files_lint_each_juliet_block_at_its_own_lines() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("docs/guide.md"),
+        "# Guide\n\n```juliet\ncreate Brief from juliet \"Summarize.\";\nhalt;\n```\n\nThen triage:\n\n```julietscript\npolicy triage = \"\"\"x\"\"\"\nhalt;\n```\n\n```sh\nnot juliet\n```\n",
+    );
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["docs/*.md"],
+        &["--relative-to", dir.path().to_str().unwrap()],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert_eq!(
+        stdout,
+        "docs/guide.md:12:1: error: Expected ';' after policy declaration.\nLinted 1 file(s): 1 issue(s) (1 error(s), 0 warning(s)).\n"
+    );
+}
+
+#[test]
+fn max_issues_gates_on_the_total_issue_count() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/bad.julietscript"),
+        "policy a = \"\"\"x\"\"\"\npolicy b = \"\"\"y\"\"\"\npolicy c = \"\"\"z\"\"\"\n",
+    );
+
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--max-issues", "3"]);
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert_eq!(output.status.code(), Some(0), "{stdout}");
+    assert!(stdout.contains("3 issue(s)"), "{stdout}");
+
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--max-issues", "2"]);
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert_eq!(output.status.code(), Some(1), "{stdout}");
+    assert!(
+        stdout.contains("Found 3 issue(s), over the --max-issues limit of 2."),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn completions_subcommand_prints_scripts_generated_from_the_parser() {
+    for shell in ["bash", "zsh", "fish", "powershell", "elvish"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+            .args(["completions", shell])
+            .output()
+            .expect("failed to run julietscript-lint");
+        assert_eq!(output.status.code(), Some(0), "{shell}");
+        let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+        assert!(stdout.contains("julietscript-lint"), "{shell}");
+        assert!(stdout.contains("quiet-clean"), "{shell}");
+        assert!(stdout.contains("validate-graph"), "{shell}");
+    }
+}
+
+#[test]
+fn timings_reports_phase_durations_on_stderr_only() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/good.julietscript"), valid_script());
+    let plain = run_lint(dir.path(), &["**/*.julietscript"]);
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--timings"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.stdout, plain.stdout);
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    for phase in ["collect:", "read:", "bridge:", "total:"] {
+        assert!(stderr.contains(phase), "{stderr}");
+    }
+    assert!(!stderr.contains("worker"), "{stderr}");
+
+    write_file(&dir.file("scripts/other.julietscript"), valid_script());
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--timings", "--jobs", "2"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    for phase in ["bridge:", "worker 1:", "worker 2:", "total:"] {
+        assert!(stderr.contains(phase), "{stderr}");
+    }
+}
+
+#[test]
+fn byte_order_mark_is_stripped_before_linting() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/bom.julietscript"),
+        &format!("\u{feff}{}", valid_script()),
+    );
+    let output = run_lint(dir.path(), &["**/*.julietscript"]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("Linted 1 file(s): 0 issue(s)"), "{stdout}");
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--enable", "byte-order-mark"],
+    );
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(
+        stdout.contains("bom.julietscript:1:1: warning: File starts with a UTF-8 byte order mark.")
+    );
+}
+
+#[test]
+fn fail_fast_stops_after_the_first_file_with_errors() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/a.julietscript"), "halt\n");
+    write_file(&dir.file("scripts/b.julietscript"), "halt\n");
+
+    let output = run_lint_with_args(dir.path(), &["scripts/*.julietscript"], &["--fail-fast"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("a.julietscript:2:1: error:"), "{stdout}");
+    assert!(!stdout.contains("b.julietscript"), "{stdout}");
+    assert!(stdout.contains("Linted 1 file(s):"), "{stdout}");
+    assert!(
+        stdout.contains("(--fail-fast); 1 of 2 file(s) linted."),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn profile_presets_change_which_rules_fail_the_run() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/warn.julietscript"),
+        "set \"k\" as \"a\";\nset \"k\" as \"b\";\n",
+    );
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--profile", "minimal"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("0 issue(s)"), "{stdout}");
+
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--profile", "strict"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("warn.julietscript:2:5: error: Duplicate global key assignment 'k'."));
+}
+
+#[test]
+fn json_positions_are_one_based_by_default_and_match_text_output() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(&dir.file("scripts/bad.julietscript"), "halt;\n  halt\n");
+
+    let text = run_lint(dir.path(), &["**/*.julietscript"]);
+    let text = String::from_utf8(text.stdout).expect("stdout should be utf8");
+    assert!(text.contains("bad.julietscript:3:1: error:"), "{text}");
+
+    let output = run_lint_with_args(dir.path(), &["**/*.julietscript"], &["--format", "json"]);
+    let results: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    let start = &results["files"][0]["diagnostics"][0]["range"]["start"];
+    assert_eq!(start["line"], 3);
+    assert_eq!(start["character"], 1);
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--format", "json", "--base", "0"],
+    );
+    let results: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    let start = &results["files"][0]["diagnostics"][0]["range"]["start"];
+    assert_eq!(start["line"], 2);
+    assert_eq!(start["character"], 0);
+}
+
+#[test]
+fn max_inflight_caps_concurrent_node_processes() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let inflight = dir.file("inflight");
+    fs::create_dir_all(&inflight).expect("failed to create inflight directory");
+    // Each bridge process registers itself while it runs and logs how many
+    // bridges it saw running alongside it.
+    write_file(
+        &dir.file("counting-linter.js"),
+        &format!(
+            r#"const fs = require("fs");
+const path = require("path");
+const inflight = {inflight:?};
+const marker = path.join(inflight, String(process.pid));
+fs.writeFileSync(marker, "");
+process.on("exit", () => fs.unlinkSync(marker));
+fs.appendFileSync(path.join(inflight, "..", "counts"), `${{fs.readdirSync(inflight).length}}\n`);
+const deadline = Date.now() + 150;
+while (Date.now() < deadline) {{}}
+function lintJulietScript() {{
+  return [];
+}}
+module.exports = {{ lintJulietScript }};
+"#,
+            inflight = inflight.display().to_string()
+        ),
+    );
+    for name in ["a", "b", "c", "d"] {
+        write_file(
+            &dir.file(&format!("scripts/{name}.julietscript")),
+            "halt;\n",
+        );
+    }
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["scripts/*.julietscript"],
+        &[
+            "--jobs",
+            "4",
+            "--max-inflight",
+            "2",
+            "--linter",
+            dir.file("counting-linter.js").to_str().unwrap(),
+        ],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("Linted 4 file(s):"), "{stdout}");
+    let counts = fs::read_to_string(dir.file("counts")).expect("counts should be written");
+    assert_eq!(counts.lines().count(), 4);
+    assert!(
+        counts
+            .lines()
+            .all(|count| count.parse::<usize>().unwrap() <= 2),
+        "{counts}"
+    );
+}
+
+#[test]
+fn exact_rubric_points_enables_the_rubric_points_rule() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/rubric.julietscript"),
+        "rubric R {\n  criterion \"A\" points 6 means \"a\";\n  criterion \"B\" points 5 means \"b\";\n}\n",
+    );
+
+    let output = run_lint(dir.path(), &["**/*.julietscript"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--exact-rubric-points", "10"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains(
+        "rubric.julietscript:1:1: error: Rubric 'R' criteria total 11 points; rubrics must total exactly 10."
+    ));
+}
+
+#[test]
+fn max_search_budget_of_zero_is_rejected() {
+    let dir = TestDir::new();
+    let output = run_lint_with_args(dir.path(), &[], &["--max-search-budget", "0"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(stderr.contains("--max-search-budget"), "{stderr}");
+
+    write_file(
+        &dir.file("julietscript-lint.json"),
+        r#"{ "max-search-budget": 0 }"#,
+    );
+    let output = run_lint_with_args(
+        dir.path(),
+        &[],
+        &[
+            "--config",
+            dir.file("julietscript-lint.json").to_str().unwrap(),
+            "--print-config",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(stderr.contains("nonzero"), "{stderr}");
+}
+
+#[test]
+fn max_search_budget_enables_the_search_budget_rule() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.file("scripts/cadence.julietscript"),
+        "rubric R {\n  criterion \"A\" points 1 means \"a\";\n}\ncadence C {\n  variants = 10;\n  sprints = 10;\n  compare using R;\n}\n",
+    );
+
+    let output = run_lint(dir.path(), &["**/*.julietscript"]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(!stdout.contains("search budget"), "{stdout}");
+
+    let output = run_lint_with_args(
+        dir.path(),
+        &["**/*.julietscript"],
+        &["--max-search-budget", "1000"],
+    );
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(
+        stdout.contains(
+            "cadence.julietscript:4:1: warning: Cadence 'C' generates 11111111110 candidates over 10 sprint(s), more than the search budget of 1000."
+        ),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn doctor_reports_runtime_version_and_linter_health() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let node_version = Command::new("node")
+        .arg("--version")
+        .output()
+        .expect("node should run");
+    let node_version = String::from_utf8(node_version.stdout).expect("version should be utf8");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg("doctor")
+        .output()
+        .expect("failed to run julietscript-lint");
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(
+        stdout.contains(&format!("PASS node: {}", node_version.trim())),
+        "{stdout}"
+    );
+    assert!(stdout.contains("PASS embedded linter:"), "{stdout}");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg("doctor")
+        .env("PATH", "")
+        .output()
+        .expect("failed to run julietscript-lint");
+    assert_eq!(output.status.code(), Some(2));
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(stdout.contains("FAIL node:"), "{stdout}");
+}
+
+#[test]
+fn stdin_allows_artifacts_created_in_other_files() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg("--root")
+        .arg(dir.path())
+        .arg("--stdin")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run julietscript-lint");
+    child
+        .stdin
+        .take()
+        .expect("stdin should be piped")
+        .write_all(b"create Plan from juliet \"Plan the work.\" using [Brief];\nhalt;\n")
+        .expect("failed to write stdin");
+    let output = child.wait_with_output().expect("lint should finish");
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert_eq!(output.status.code(), Some(0), "{stdout}");
+    assert!(!stdout.contains("Unknown artifact"), "{stdout}");
+}
+
+#[test]
+fn stdin_ndjson_lints_each_document_under_its_own_path() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_julietscript-lint"))
+        .arg("--root")
+        .arg(dir.path())
+        .args(["--stdin", "--stdin-format", "ndjson", "--format", "json"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run julietscript-lint");
+    child
+        .stdin
+        .take()
+        .expect("stdin should be piped")
+        .write_all(
+            b"{\"path\":\"buffers/one.julietscript\",\"source\":\"halt;\\n\"}\n\
+              {\"path\":\"buffers/two.julietscript\",\"source\":\"halt\\n\"}\n",
+        )
+        .expect("failed to write stdin");
+    let output = child.wait_with_output().expect("lint should finish");
+    assert_eq!(output.status.code(), Some(1));
+
+    let results: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    let paths: Vec<&str> = results["files"]
+        .as_array()
+        .expect("files should be an array")
+        .iter()
+        .map(|result| result["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        paths,
+        ["buffers/one.julietscript", "buffers/two.julietscript"]
+    );
+    assert_eq!(
+        results["files"][0]["diagnostics"].as_array().unwrap().len(),
+        0
+    );
+    assert!(!results["files"][1]["diagnostics"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,90 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use julietscript_lint::{lint_directory, LintOptions};
+
+struct TestDir {
+    path: PathBuf,
+}
+
+impl TestDir {
+    fn new() -> Self {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be after unix epoch")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!(
+            "julietscript-lint-library-tests-{}-{}",
+            std::process::id(),
+            unique
+        ));
+        fs::create_dir_all(&path).expect("failed to create temporary test directory");
+        Self { path }
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+fn write_file(path: &Path, content: &str) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("failed to create parent directory");
+    }
+    fs::write(path, content).expect("failed to write file");
+}
+
+fn has_node() -> bool {
+    Command::new("node")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+#[test]
+fn lint_directory_reports_per_file_diagnostics_and_counts() {
+    if !has_node() {
+        eprintln!("Skipping test: node is not available.");
+        return;
+    }
+
+    let dir = TestDir::new();
+    write_file(
+        &dir.path.join("scripts/warn.julietscript"),
+        "set \"k\" as \"a\";\nset \"k\" as \"b\";\n",
+    );
+    write_file(
+        &dir.path.join("scripts/broken.julietscript"),
+        "create Plan from juliet \"Plan it.\" using [Missing];\n",
+    );
+    write_file(&dir.path.join("vendor/skip.julietscript"), "halt\n");
+
+    let options = LintOptions {
+        excludes: vec!["vendor/**".to_owned()],
+        ..LintOptions::default()
+    };
+    let report = lint_directory(&dir.path, &[], &options).expect("lint should run");
+    assert_eq!(report.summary.files, 2);
+    assert_eq!(report.summary.issues, 2);
+    assert_eq!(report.summary.errors, 1);
+    assert_eq!(report.summary.warnings, 1);
+    assert!(report.files[0].path.ends_with("broken.julietscript"));
+    assert!(report.files[1].path.ends_with("warn.julietscript"));
+
+    let options = LintOptions {
+        excludes: vec!["vendor/**".to_owned()],
+        disabled_rules: vec!["duplicate-global".to_owned()],
+        ..LintOptions::default()
+    };
+    let report =
+        lint_directory(&dir.path, &["scripts/*.julietscript"], &options).expect("lint should run");
+    assert_eq!(report.summary.issues, 1);
+    assert_eq!(report.summary.errors, 1);
+}
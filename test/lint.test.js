@@ -1,7 +1,7 @@
 "use strict";
 
 const assert = require("assert");
-const { lintJulietScript, SEVERITY } = require("../src/linter");
+const { dedentBlockString, lintJulietScript, parseJulietScript, SEVERITY } = require("../src/linter");
 
 function countBySeverity(diagnostics, severity) {
   return diagnostics.filter((entry) => entry.severity === severity).length;
@@ -156,7 +156,7 @@ create Phase1WebGLFoundation from julietArtifactSourceFiles [];
     source: `
 policy failureTriage = """x""";
 rubric quality {
-  criterion "Spec" points 1;
+  criterion "Spec" points 1 means "Matches the specification.";
 }
 cadence c {
   variants = 1;
@@ -180,7 +180,7 @@ extend A.prompt with """nope""";
     name: "reports keep best without integer limit",
     source: `
 rubric quality {
-  criterion "Spec" points 1;
+  criterion "Spec" points 1 means "Matches the specification.";
 }
 cadence c {
   variants = 2;
@@ -206,6 +206,1280 @@ rubric quality {
       assert.match(errorMessages, /Expected criterion meaning string after 'means'/);
     }
   },
+  {
+    name: "warns when cadence sprints is zero",
+    source: `
+rubric quality {
+  criterion "Spec" points 1 means "Matches the specification.";
+}
+cadence c {
+  variants = 2;
+  sprints = 0;
+  compare using quality;
+  keep best 1;
+}
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(countBySeverity(diagnostics, SEVERITY.ERROR), 0);
+      const warnings = diagnostics.filter((entry) => entry.severity === SEVERITY.WARNING);
+      assert.strictEqual(warnings.length, 1);
+      assert.match(warnings[0].message, /Cadence 'sprints' is 0, so no iterations will run/);
+      assert.strictEqual(warnings[0].range.start.line, 6);
+    }
+  },
+  {
+    name: "warns when a referenced cadence is missing variants",
+    source: `
+rubric quality {
+  criterion "Spec" points 1 means "Matches the specification.";
+}
+cadence c {
+  sprints = 1;
+  compare using quality;
+  keep best 1;
+}
+create A from juliet """prompt""" with {
+  cadence = c;
+};
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(countBySeverity(diagnostics, SEVERITY.ERROR), 0);
+      const warnings = diagnostics.filter((entry) => entry.severity === SEVERITY.WARNING);
+      assert.strictEqual(warnings.length, 1);
+      assert.match(warnings[0].message, /Cadence is missing required key 'variants'/);
+      assert.strictEqual(warnings[0].range.start.line, 4);
+    }
+  },
+  {
+    name: "ignores missing cadence keys when the cadence is never referenced",
+    source: `
+cadence c {
+  sprints = 1;
+}
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(countBySeverity(diagnostics, SEVERITY.ERROR), 0);
+      assert.strictEqual(countBySeverity(diagnostics, SEVERITY.WARNING), 0);
+    }
+  },
+  {
+    name: "accepts complete referenced cadence",
+    source: `
+rubric quality {
+  criterion "Spec" points 1 means "Matches the specification.";
+}
+cadence c {
+  variants = 2;
+  sprints = 1;
+  compare using quality;
+  keep best 1;
+}
+create A from juliet """prompt""" with {
+  cadence = c;
+};
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(countBySeverity(diagnostics, SEVERITY.ERROR), 0);
+      assert.strictEqual(countBySeverity(diagnostics, SEVERITY.WARNING), 0);
+    }
+  },
+  {
+    name: "reports cadence referenced before it is declared",
+    source: `
+rubric quality {
+  criterion "Spec" points 1 means "Matches the specification.";
+}
+create A from juliet """prompt""" with {
+  cadence = ShipLoop;
+};
+cadence ShipLoop {
+  variants = 1;
+  sprints = 1;
+  compare using quality;
+  keep best 1;
+}
+`,
+    validate: (diagnostics) => {
+      const errors = diagnostics.filter((entry) => entry.severity === SEVERITY.ERROR);
+      assert.strictEqual(errors.length, 1);
+      assert.match(errors[0].message, /Cadence 'ShipLoop' is referenced before it is declared on line 8/);
+      assert.strictEqual(errors[0].range.start.line, 5);
+    }
+  },
+  {
+    name: "reports artifact used before it is created",
+    source: `
+create Plan from juliet "Plan it." using [Brief];
+create Brief from julietArtifactSourceFiles ["./brief.md"];
+`,
+    validate: (diagnostics) => {
+      const errorMessages = messages(diagnostics).join("\n");
+      assert.match(errorMessages, /Artifact 'Brief' is referenced before it is declared/);
+      assert.doesNotMatch(errorMessages, /Unknown artifact/);
+    }
+  },
+  {
+    name: "warns when a criterion has no means clause",
+    source: `
+rubric quality {
+  criterion "Clarity" points 3;
+}
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(countBySeverity(diagnostics, SEVERITY.ERROR), 0);
+      const warnings = diagnostics.filter((entry) => entry.severity === SEVERITY.WARNING);
+      assert.strictEqual(warnings.length, 1);
+      assert.match(warnings[0].message, /Criterion 'Clarity' has no 'means' description/);
+      assert.strictEqual(warnings[0].range.start.line, 2);
+    }
+  },
+  {
+    name: "reports empty criterion means as an error",
+    source: `
+rubric quality {
+  criterion "Clarity" points 3 means "";
+}
+`,
+    validate: (diagnostics) => {
+      const errors = diagnostics.filter((entry) => entry.severity === SEVERITY.ERROR);
+      assert.strictEqual(errors.length, 1);
+      assert.match(errors[0].message, /Criterion meaning should not be empty/);
+    }
+  },
+  {
+    name: "accepts criterion with points and means",
+    source: `
+rubric quality {
+  criterion "Clarity" points 3 means "Writing is easy to scan.";
+}
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(countBySeverity(diagnostics, SEVERITY.ERROR), 0);
+      assert.strictEqual(countBySeverity(diagnostics, SEVERITY.WARNING), 0);
+    }
+  },
+  {
+    name: "warns on empty extend guidance",
+    source: `
+rubric Safety {
+  criterion "Safe" points 1 means "No data loss.";
+}
+create A from juliet "Write the migration." with { rubric = Safety; };
+extend A.rubric with "  ";
+extend A.rubric with """
+
+""";
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(countBySeverity(diagnostics, SEVERITY.ERROR), 0);
+      const warnings = diagnostics.filter((entry) => entry.severity === SEVERITY.WARNING);
+      assert.strictEqual(warnings.length, 2);
+      for (const warning of warnings) {
+        assert.match(warning.message, /Extend guidance should not be empty/);
+      }
+      assert.deepStrictEqual(warnings.map((entry) => entry.range.start.line), [5, 6]);
+    }
+  },
+  {
+    name: "accepts non-empty extend guidance",
+    source: `
+rubric Safety {
+  criterion "Safe" points 1 means "No data loss.";
+}
+create A from juliet "Write the migration." with { rubric = Safety; };
+extend A.rubric with "Check migration safety.";
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(countBySeverity(diagnostics, SEVERITY.ERROR), 0);
+      assert.strictEqual(countBySeverity(diagnostics, SEVERITY.WARNING), 0);
+    }
+  },
+  {
+    name: "tags diagnostics with rule ids",
+    source: `
+set "k" as "a";
+set "k" as "b";
+policy triage = """x"""
+halt
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(diagnostics.map((entry) => entry.rule), [
+        "duplicate-global",
+        "missing-semicolon",
+        "syntax",
+        "missing-semicolon"
+      ]);
+    }
+  },
+  {
+    name: "reports fractional cadence variants",
+    source: `
+cadence c {
+  variants = 3.5;
+  sprints = 1;
+}
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Expected an integer for cadence key 'variants', found '3.5'."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "numeric-literal");
+      assert.strictEqual(diagnostics[0].range.start.character, 13);
+      assert.strictEqual(diagnostics[0].range.end.character, 16);
+    }
+  },
+  {
+    name: "reports malformed criterion points",
+    source: `
+rubric quality {
+  criterion "Spec" points 2abc means "Matches the specification.";
+}
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Expected an integer for criterion points, found '2abc'."
+      ]);
+    }
+  },
+  {
+    name: "accepts consistent two-space indentation when indentation rule is enabled",
+    options: { enable: ["indentation"] },
+    source: `
+cadence c {
+  variants = 1;
+  sprints = 1;
+}
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "accepts consistent tab indentation when indentation rule is enabled",
+    options: { enable: ["indentation"] },
+    source: "cadence c {\n\tvariants = 1;\n\tsprints = 1;\n}\n",
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "warns on tabs and spaces mixed within a block",
+    options: { enable: ["indentation"] },
+    source: "cadence c {\n  variants = 1;\n\tsprints = 1;\n\tkeep best 1;\n}\n",
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Block mixes tabs and spaces: this line is indented with tabs, earlier lines with spaces."
+      ]);
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.strictEqual(diagnostics[0].rule, "indentation");
+      assert.strictEqual(diagnostics[0].range.start.line, 2);
+    }
+  },
+  {
+    name: "warns on indentation that is not a multiple of two",
+    options: { enable: ["indentation"] },
+    source: `
+cadence c {
+   variants = 1;
+  sprints = 1;
+}
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Indentation should be a multiple of two spaces, found 3."
+      ]);
+    }
+  },
+  {
+    name: "does not check indentation unless enabled",
+    source: "cadence c {\n  variants = 1;\n\tsprints = 1;\n}\n",
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "drops diagnostics for disabled rules",
+    options: { disable: ["duplicate-global"] },
+    source: `
+set "k" as "a";
+set "k" as "b";
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "reports a duplicated juliet block as an error",
+    source: `
+juliet {
+  engine = codex;
+}
+
+juliet {
+  engine = codex;
+}
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Duplicate juliet block. Only one top-level juliet block is allowed."
+      ]);
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.ERROR);
+      assert.strictEqual(diagnostics[0].rule, "juliet-block");
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 5, character: 0 });
+      assert.deepStrictEqual(diagnostics[0].range.end, { line: 5, character: 6 });
+    }
+  },
+  {
+    name: "warns when the juliet block is not first",
+    source: `
+policy triage = """Retry once.""";
+
+juliet {
+  engine = codex;
+}
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "The juliet block should be the first top-level block so its defaults apply to everything after it."
+      ]);
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 3, character: 0 });
+    }
+  },
+  {
+    name: "offers a fix that removes a duplicate source path",
+    source: `create Notes from julietArtifactSourceFiles ["a.md", "a.md"];`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 1);
+      assert.strictEqual(diagnostics[0].rule, "duplicate-source-path");
+      assert.deepStrictEqual(diagnostics[0].fix, {
+        range: { start: { line: 0, character: 51 }, end: { line: 0, character: 59 } },
+        text: ""
+      });
+    }
+  },
+  {
+    name: "reports a hyphenated artifact name once",
+    source: `create my-artifact from juliet "Summarize the repo.";\nhalt;\n`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Name 'my-artifact' contains '-'; names may only use letters, digits, and '_'."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "identifier-name");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.ERROR);
+      assert.deepStrictEqual(diagnostics[0].range, {
+        start: { line: 0, character: 7 },
+        end: { line: 0, character: 18 }
+      });
+    }
+  },
+  {
+    name: "reports a name that starts with a digit",
+    source: `create 2brief from juliet "Summarize the repo.";\nhalt;\n`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Name '2brief' starts with '2'; names must start with a letter."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "identifier-name");
+    }
+  },
+  {
+    name: "accepts names made of letters, digits, and underscores",
+    source: `create my_artifact2 from juliet "Summarize the repo.";\nhalt;\n`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "treats a leading ./ as the same source path",
+    source: `create Notes from julietArtifactSourceFiles ["a.md", "b.md", "./a.md"];`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Duplicate source file path './a.md' in julietArtifactSourceFiles list."
+      ]);
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 0, character: 61 });
+    }
+  },
+  {
+    name: "accepts a source files list without repeats",
+    source: `create Notes from julietArtifactSourceFiles ["a.md", "docs/a.md", "./b.md"];`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "warns on an intermediate artifact nothing consumes when orphaned-artifact is enabled",
+    options: { enable: ["orphaned-artifact"] },
+    source: `
+create Brief from juliet "Summarize the repo.";
+create Notes from juliet "Collect open questions.";
+create Plan from juliet "Plan the work." using [Brief];
+halt;
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Artifact 'Notes' is never used by a later create and is not a final output; did you forget to add it to a 'using' list?"
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "orphaned-artifact");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 2, character: 7 });
+    }
+  },
+  {
+    name: "accepts a clean artifact chain and outputs before each halt",
+    options: { enable: ["orphaned-artifact"] },
+    source: `
+create Brief from juliet "Summarize the repo.";
+create Draft from juliet "Draft a memo." using [Brief];
+halt "Review the draft first.";
+create Plan from juliet "Plan the work." using [Brief];
+create Patch from juliet "Implement the plan." using [Plan];
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "warns on a control character in a policy body",
+    source: 'policy triage = """\nRetry once.\nThen\u0007 stop.\n""";\n',
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Policy body contains control character U+0007."
+      ]);
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.strictEqual(diagnostics[0].rule, "control-character");
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 2, character: 4 });
+    }
+  },
+  {
+    name: "warns on a prompt string that starts with a byte order mark",
+    source: 'create Memo from juliet "\uFEFFWrite a memo.";\n',
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Prompt starts with a byte order mark (U+FEFF)."
+      ]);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 0, character: 25 });
+    }
+  },
+  {
+    name: "allows tabs and CRLF line endings in policy bodies",
+    source: 'policy triage = """\r\n\tRetry once.\r\n""";\r\n',
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "warns on an overlong comment line when max-line-length is enabled",
+    options: { enable: ["max-line-length"], maxLineLength: 40 },
+    source: `# ${"x".repeat(48)}\nhalt;\n`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Line is 50 characters long; the limit is 40."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "max-line-length");
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 0, character: 40 });
+      assert.deepStrictEqual(diagnostics[0].range.end, { line: 0, character: 50 });
+    }
+  },
+  {
+    name: "ignores long lines inside triple-quoted strings and defaults to 120",
+    options: { enable: ["max-line-length"] },
+    source: `policy triage = """\n${"word ".repeat(40)}\n""";\n# ${"y".repeat(110)}\n`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "warns on ragged indentation inside a triple-quoted policy",
+    source: `
+policy PreflightChecklist = """
+    Before sprinting:
+    - restate scope
+- list risky files
+    """;
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Block string line is indented less than its first line (0 vs 4), so the block cannot be dedented consistently."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "block-string-indentation");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 4, character: 0 });
+      assert.deepStrictEqual(diagnostics[0].range.end, { line: 4, character: 18 });
+    }
+  },
+  {
+    name: "accepts uniformly indented and flush-left block strings with nested lists",
+    source: `
+policy Indented = """
+    Before sprinting:
+      - restate scope
+    """;
+
+policy Flush = """
+Before sprinting:
+  - restate scope
+- list risky files
+""";
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "warns when a with block is attached to a source-seeded create",
+    source: `
+policy Preflight = """
+Confirm scope.
+""";
+
+create Notes from julietArtifactSourceFiles ["notes.md"]
+with {
+  preflight = Preflight;
+};
+
+create Specs from julietArtifactSourceFiles ["spec.md"];
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Artifact 'Notes' is seeded from julietArtifactSourceFiles and has no generation run, so its 'with' attachments have no effect."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "seeded-attachments");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 6, character: 0 });
+      assert.deepStrictEqual(diagnostics[0].range.end, { line: 6, character: 4 });
+    }
+  },
+  {
+    name: "warns when an opted-in cadence engine differs from the juliet default",
+    options: { enable: ["engine-conflict"] },
+    source: `
+juliet {
+  engine = codex;
+}
+
+cadence ShipLoop {
+  engine = claude;
+  variants = 1;
+  sprints = 1;
+}
+
+cadence SameLoop {
+  engine = codex;
+  variants = 1;
+  sprints = 1;
+}
+
+create Memo from juliet "Write a memo."
+with {
+  cadence = ShipLoop;
+};
+
+create Summary from juliet "Summarize the memo." using [Memo]
+with {
+  cadence = SameLoop;
+};
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Artifact 'Memo' runs on engine 'claude' from cadence 'ShipLoop', not the juliet default 'codex'. A cadence's engine takes precedence over the juliet block."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "engine-conflict");
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 19, character: 12 });
+    }
+  },
+  {
+    name: "does not report engine conflicts unless enabled",
+    source: `
+juliet {
+  engine = codex;
+}
+
+cadence ShipLoop {
+  engine = claude;
+  variants = 1;
+  sprints = 1;
+}
+
+create Memo from juliet "Write a memo."
+with {
+  cadence = ShipLoop;
+};
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "ignores a leading byte order mark unless byte-order-mark is enabled",
+    source: "\uFEFFhalt;\n",
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+      const enabled = lintJulietScript("\uFEFFhalt;\n", { enable: ["byte-order-mark"] });
+      assert.deepStrictEqual(messages(enabled), ["File starts with a UTF-8 byte order mark."]);
+      assert.strictEqual(enabled[0].rule, "byte-order-mark");
+      assert.strictEqual(enabled[0].severity, SEVERITY.WARNING);
+    }
+  },
+  {
+    name: "reports a missing closing brace once instead of cascading",
+    source: `
+rubric MemoRubric {
+  criterion "Accuracy" points 5 means "Facts are correct.";
+
+create Memo from juliet "Write a memo."
+with {
+  rubric = MemoRubric;
+};
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), ["Unmatched '{' for block opened here."]);
+      assert.strictEqual(diagnostics[0].rule, "unbalanced-delimiter");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.ERROR);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 1, character: 18 });
+    }
+  },
+  {
+    name: "reports stray and mismatched delimiters",
+    source: `
+halt;
+]
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), ["Unmatched ']' with no open '['."]);
+
+      const mismatched = lintJulietScript('create Notes from julietArtifactSourceFiles ["a.md"};\n');
+      assert.deepStrictEqual(messages(mismatched), [
+        "Unmatched '[' for list opened here; found '}' before its closing ']'."
+      ]);
+      assert.deepStrictEqual(mismatched[0].range.start, { line: 0, character: 44 });
+
+      const unterminated = lintJulietScript('policy P = """\nNever closed.\n\nhalt;\n');
+      assert.deepStrictEqual(messages(unterminated), ["Unterminated block string."]);
+    }
+  },
+  {
+    name: "profiles select rule presets with enable and disable layered on top",
+    options: { profile: "minimal" },
+    source: `
+set "k" as "a";
+set "k" as "b";
+halt
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Expected optional halt message string before ';'.",
+        "Expected ';' after halt statement."
+      ]);
+
+      const source = 'set "k" as "a";\nset "k" as "b";\nhalt;\n';
+      const strict = lintJulietScript(source, { profile: "strict", disable: ["orphaned-artifact"] });
+      assert.deepStrictEqual(strict.map((diagnostic) => [diagnostic.rule, diagnostic.severity]), [
+        ["duplicate-global", SEVERITY.ERROR]
+      ]);
+      const layered = lintJulietScript(source, { profile: "minimal", enable: ["duplicate-global"] });
+      assert.deepStrictEqual(layered.map((diagnostic) => diagnostic.severity), [SEVERITY.WARNING]);
+    }
+  },
+  {
+    name: "checks rubric point totals against an exact constraint when enabled",
+    options: { enable: ["rubric-points"], exactRubricPoints: 10 },
+    source: `
+rubric Eleven {
+  criterion "Accuracy" points 6 means "Facts are correct.";
+  criterion "Clarity" points 5 means "Easy to scan.";
+}
+
+rubric Ten {
+  criterion "Accuracy" points 6 means "Facts are correct.";
+  criterion "Clarity" points 4 means "Easy to scan.";
+}
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Rubric 'Eleven' criteria total 11 points; rubrics must total exactly 10."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "rubric-points");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.ERROR);
+      assert.deepStrictEqual(diagnostics[0].range, {
+        start: { line: 1, character: 0 },
+        end: { line: 4, character: 1 }
+      });
+    }
+  },
+  {
+    name: "warns when rubric points exceed a maximum",
+    options: { enable: ["rubric-points"], maxRubricPoints: 10 },
+    source: `
+rubric Eleven {
+  criterion "Accuracy" points 6 means "Facts are correct.";
+  criterion "Clarity" points 5 means "Easy to scan.";
+}
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Rubric 'Eleven' criteria total 11 points, more than the maximum of 10."
+      ]);
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+    }
+  },
+  {
+    name: "warns when a cadence's candidate count exceeds the search budget",
+    options: { enable: ["search-budget"], maxSearchBudget: 100 },
+    source: `
+rubric R {
+  criterion "Accuracy" points 1 means "Facts are correct.";
+}
+cadence Wide {
+  variants = 10;
+  sprints = 10;
+  compare using R;
+  keep best 2;
+}
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Cadence 'Wide' generates 190 candidates over 10 sprint(s), more than the search budget of 100."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "search-budget");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range, {
+        start: { line: 4, character: 0 },
+        end: { line: 9, character: 1 }
+      });
+    }
+  },
+  {
+    name: "accepts a cadence whose candidate count equals the search budget",
+    options: { enable: ["search-budget"], maxSearchBudget: 190 },
+    source: `
+rubric R {
+  criterion "Accuracy" points 1 means "Facts are correct.";
+}
+cadence Wide {
+  variants = 10;
+  sprints = 10;
+  compare using R;
+  keep best 2;
+}
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "accepts a cadence within the default search budget",
+    options: { enable: ["search-budget"] },
+    source: `
+rubric R {
+  criterion "Accuracy" points 1 means "Facts are correct.";
+}
+cadence Modest {
+  variants = 4;
+  sprints = 3;
+  compare using R;
+  keep best 2;
+}
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "warns when a criterion lists means before points",
+    source: `
+rubric MemoRubric {
+  criterion "Accuracy" means "Facts are correct." points 5;
+  criterion "Clarity" points 3 means "Easy to scan.";
+}
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Criterion 'Accuracy' lists 'means' before 'points'; write points N means \"...\" instead."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "criterion-order");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 2, character: 23 });
+    }
+  },
+  {
+    name: "reports a trailing comma once in a using list",
+    source: `
+create Brief from juliet "Summarize the repo.";
+create Plan from juliet "Plan the work." using [Brief,];
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), ["Trailing comma is not allowed in using list."]);
+      assert.strictEqual(diagnostics[0].rule, "trailing-comma");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.ERROR);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 2, character: 53 });
+      assert.deepStrictEqual(diagnostics[0].fix, {
+        range: { start: { line: 2, character: 53 }, end: { line: 2, character: 54 } },
+        text: ""
+      });
+    }
+  },
+  {
+    name: "reports a trailing comma once in a source files list",
+    source: `
+create Brief from julietArtifactSourceFiles ["./a.md", "./b.md",];
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Trailing comma is not allowed in julietArtifactSourceFiles list."
+      ]);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 1, character: 63 });
+    }
+  },
+  {
+    name: "reports a trailing comma once in a tiebreakers list",
+    source: `
+rubric quality {
+  criterion "Clarity" points 3 means "Easy to scan.";
+  tiebreakers ["Clarity",];
+}
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), ["Trailing comma is not allowed in tiebreakers list."]);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 3, character: 24 });
+    }
+  },
+  {
+    name: "warns on a halt with an empty message",
+    source: `
+create Brief from juliet "Summarize the repo.";
+halt "";
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Halt message should not be empty; add a reason or use a bare 'halt;'."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "empty-halt-message");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range, {
+        start: { line: 2, character: 5 },
+        end: { line: 2, character: 7 }
+      });
+    }
+  },
+  {
+    name: "accepts a bare halt and a halt with a message",
+    source: `
+create Brief from juliet "Summarize the repo.";
+halt "Review the brief first.";
+create Plan from juliet "Plan the work." using [Brief];
+halt;
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "warns on a single-line triple-quoted string when quote-style is enabled",
+    options: { enable: ["quote-style"] },
+    source: 'policy triage = """Retry once.""";\npolicy quoted = """Say "done" when finished.""";\n',
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Single-line string uses triple quotes; use plain quotes instead.",
+        "Single-line string uses triple quotes; use plain quotes instead."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "quote-style");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].fix, {
+        range: { start: { line: 0, character: 16 }, end: { line: 0, character: 33 } },
+        text: '"Retry once."'
+      });
+      assert.strictEqual(diagnostics[1].fix, undefined);
+    }
+  },
+  {
+    name: "accepts canonical quote styles when quote-style is enabled",
+    options: { enable: ["quote-style"] },
+    source: 'policy triage = "Retry once.";\npolicy escalate = """\nRetry once.\nThen stop.\n""";\n',
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "warns on a repeated prompt when duplicate-prompt is enabled",
+    options: { enable: ["duplicate-prompt"] },
+    source: `
+create Brief from juliet "Summarize the repo.";
+create Plan from juliet "Plan the work." using [Brief];
+create Recap from juliet """
+  Summarize   the repo.
+""" using [Plan];
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Artifact 'Recap' has the same prompt as 'Brief'; did you mean to edit one of them?"
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "duplicate-prompt");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 3, character: 25 });
+    }
+  },
+  {
+    name: "accepts distinct prompts when duplicate-prompt is enabled",
+    options: { enable: ["duplicate-prompt"] },
+    source: `
+create Brief from juliet "Summarize the repo.";
+create Plan from juliet "Plan the work." using [Brief];
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "skips unknown artifact references when artifacts may be external",
+    options: { externalArtifacts: true },
+    source: `
+create Plan from juliet "Plan the work." using [Brief];
+create Patch from juliet "Implement it." using [Plan] with { cadence = Missing; };
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), ["Unknown cadence 'Missing' referenced by 'cadence'."]);
+    }
+  },
+  {
+    name: "reports a rubric named in a using list",
+    source: `
+rubric ShipRubric {
+  criterion "Correctness" points 5 means "Tests pass.";
+}
+create Brief from juliet "Summarize the repo.";
+create Plan from juliet "Plan the work." using [Brief, ShipRubric];
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "'ShipRubric' is a rubric, not an artifact; 'using' only accepts artifacts made by 'create'."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "using-non-artifact");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.ERROR);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 5, character: 55 });
+    }
+  },
+  {
+    name: "warns on policies with empty bodies",
+    source: 'policy Placeholder = "";\npolicy Blank = """\n\n""";\npolicy Triage = "Retry once, then escalate.";\n',
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Policy 'Placeholder' has an empty body and contributes nothing when attached.",
+        "Policy 'Blank' has an empty body and contributes nothing when attached."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "empty-policy");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range, {
+        start: { line: 0, character: 21 },
+        end: { line: 0, character: 23 }
+      });
+      assert.deepStrictEqual(diagnostics[1].range.start, { line: 1, character: 15 });
+    }
+  },
+  {
+    name: "warns at end of file on a script without halt when require-halt is enabled",
+    options: { enable: ["require-halt"] },
+    source: 'create Brief from juliet "Summarize the repo.";\n',
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Script has no 'halt' statement; end it with 'halt;' so termination is explicit."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "require-halt");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 1, character: 0 });
+    }
+  },
+  {
+    name: "accepts a script ending in halt when require-halt is enabled",
+    options: { enable: ["require-halt"] },
+    source: 'create Brief from juliet "Summarize the repo.";\nhalt;\n',
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "warns at the last line when a file has no final newline and final-newline is enabled",
+    options: { enable: ["final-newline"] },
+    source: "create Brief from juliet \"Summarize the repo.\";\nhalt;",
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), ["File does not end with a newline."]);
+      assert.strictEqual(diagnostics[0].rule, "final-newline");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].fix, {
+        range: { start: { line: 1, character: 5 }, end: { line: 1, character: 5 } },
+        text: "\n"
+      });
+    }
+  },
+  {
+    name: "offers a fix that trims extra final newlines",
+    options: { enable: ["final-newline"] },
+    source: "halt;\n\n\n",
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), ["File ends with more than one newline."]);
+      assert.deepStrictEqual(diagnostics[0].fix, {
+        range: { start: { line: 0, character: 5 }, end: { line: 3, character: 0 } },
+        text: "\n"
+      });
+    }
+  },
+  {
+    name: "accepts a file ending in exactly one newline when final-newline is enabled",
+    options: { enable: ["final-newline"] },
+    source: "create Brief from juliet \"Summarize the repo.\";\nhalt;\n",
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "warns when extending the rubric of an artifact without one",
+    source: `
+create Brief from juliet "Summarize the repo.";
+extend Brief.rubric with "Check the summary is accurate.";
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Artifact 'Brief' has no 'rubric' attachment, so there is no rubric to extend."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "extend-without-rubric");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 2, character: 13 });
+    }
+  },
+  {
+    name: "warns when extending the rubric of a seed artifact",
+    source: `
+create Brief from julietArtifactSourceFiles ["./brief.md"];
+extend Brief.rubric with "Check the summary is accurate.";
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Artifact 'Brief' is seeded from julietArtifactSourceFiles and is never scored, so it has no rubric to extend."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "extend-seed-rubric");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 2, character: 0 });
+      assert.deepStrictEqual(diagnostics[0].range.end, { line: 2, character: 58 });
+    }
+  },
+  {
+    name: "accepts extending the rubric of a generated artifact",
+    source: `
+rubric BriefRubric {
+  criterion "Accuracy" points 1 means "Matches the repo.";
+}
+create Brief from juliet "Summarize the repo." with { rubric = BriefRubric; };
+extend Brief.rubric with "Check the summary is accurate.";
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "warns on criterion names that repeat or differ only in case",
+    source: `
+rubric ShipRubric {
+  criterion "Safety" points 3 means "No data loss.";
+  criterion "safety" points 2 means "No regressions.";
+  criterion "Safety" points 1 means "Rollback exists.";
+  criterion "Speed" points 1 means "Fast enough.";
+}
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Criterion 'safety' differs only in case from 'Safety' in rubric 'ShipRubric'; spell it 'Safety'.",
+        "Duplicate criterion 'Safety' in rubric 'ShipRubric'."
+      ]);
+      assert.deepStrictEqual(diagnostics.map((entry) => entry.rule), ["criterion-case", "duplicate-criterion"]);
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 3, character: 12 });
+    }
+  },
+  {
+    name: "accepts distinct criterion names in a rubric",
+    source: `
+rubric ShipRubric {
+  criterion "Safety" points 3 means "No data loss.";
+  criterion "Speed" points 1 means "Fast enough.";
+}
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "reports declarations named after reserved keywords",
+    source: `
+policy using = "Cite sources.";
+create halt from juliet "Draft the memo.";
+halt;
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Policy name 'using' is a reserved keyword; choose a different name.",
+        "Artifact name 'halt' is a reserved keyword; choose a different name."
+      ]);
+      assert.ok(diagnostics.every((entry) => entry.rule === "reserved-name" && entry.severity === SEVERITY.ERROR));
+      assert.deepStrictEqual(diagnostics[1].range.start, { line: 2, character: 7 });
+    }
+  },
+  {
+    name: "accepts declaration names that are not reserved",
+    source: `
+create Memo from juliet "Draft the memo.";
+halt;
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "warns on an empty with-block on a create",
+    source: `
+create Memo from juliet "Draft the memo." with { };
+halt;
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Artifact 'Memo' has an empty 'with { }' block; remove it or add attachments."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "empty-attachments");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range.start, { line: 1, character: 42 });
+    }
+  },
+  {
+    name: "accepts a populated with-block on a create",
+    source: `
+policy Tone = "Be brief.";
+create Memo from juliet "Draft the memo." with { preflight = Tone; };
+halt;
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "reports quoted values for numeric attributes",
+    source: `
+rubric Quality {
+  criterion "Accuracy" points "5" means "Facts are correct.";
+}
+cadence Loop {
+  variants = "3";
+  sprints = 2;
+  compare using Quality;
+  keep best "1";
+}
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Expected an integer for criterion points, found the quoted string \"5\"; remove the quotes.",
+        "Expected an integer for cadence key 'variants', found the quoted string \"3\"; remove the quotes.",
+        "Expected an integer for 'keep best', found the quoted string \"1\"; remove the quotes."
+      ]);
+      assert.ok(diagnostics.every((entry) => entry.rule === "quoted-number" && entry.severity === SEVERITY.ERROR));
+      assert.deepStrictEqual(diagnostics[1].range.start, { line: 5, character: 13 });
+    }
+  },
+  {
+    name: "accepts unquoted numeric attributes",
+    source: `
+rubric Quality {
+  criterion "Accuracy" points 5 means "Facts are correct.";
+}
+cadence Loop {
+  variants = 3;
+  sprints = 2;
+  compare using Quality;
+  keep best 1;
+}
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "warns when a multi-variant cadence has no compare using",
+    source: `
+cadence Loop {
+  variants = 3;
+  sprints = 2;
+}
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Cadence 'Loop' generates 3 variants but has no 'compare using <rubric>;' to rank them."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "cadence-missing-compare");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range, {
+        start: { line: 1, character: 0 },
+        end: { line: 4, character: 1 }
+      });
+    }
+  },
+  {
+    name: "accepts a multi-variant cadence that compares candidates",
+    source: `
+rubric Quality {
+  criterion "Accuracy" points 5 means "Facts are correct.";
+}
+cadence Loop {
+  variants = 3;
+  sprints = 2;
+  compare using Quality;
+  keep best 1;
+}
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+    }
+  },
+  {
+    name: "warns on a generation create with no engine when missing-engine is enabled",
+    options: { enable: ["missing-engine"] },
+    source: `
+cadence Loop {
+  variants = 1;
+  sprints = 1;
+}
+create Seed from julietArtifactSourceFiles ["notes.md"];
+create Memo from juliet "Draft the memo." using [Seed] with { cadence = Loop; };
+halt;
+`,
+    validate: (diagnostics) => {
+      assert.deepStrictEqual(messages(diagnostics), [
+        "Artifact 'Memo' is generated from juliet but no engine is declared; set 'engine' in the juliet block or in its cadence."
+      ]);
+      assert.strictEqual(diagnostics[0].rule, "missing-engine");
+      assert.strictEqual(diagnostics[0].severity, SEVERITY.WARNING);
+      assert.deepStrictEqual(diagnostics[0].range, {
+        start: { line: 6, character: 0 },
+        end: { line: 6, character: 80 }
+      });
+    }
+  },
+  {
+    name: "accepts generation creates with a juliet or cadence engine when missing-engine is enabled",
+    options: { enable: ["missing-engine"] },
+    source: `
+juliet {
+  engine = codex;
+}
+create Memo from juliet "Draft the memo.";
+halt;
+`,
+    validate: (diagnostics) => {
+      assert.strictEqual(diagnostics.length, 0);
+      const viaCadence = lintJulietScript(
+        'cadence Loop {\n  engine = codex;\n  variants = 1;\n  sprints = 1;\n}\ncreate Memo from juliet "Draft." with { cadence = Loop; };\n',
+        { enable: ["missing-engine"] }
+      );
+      assert.strictEqual(viaCadence.length, 0);
+    }
+  },
   {
     name: "reports syntax issues",
     source: `
@@ -218,8 +1492,18 @@ halt
   }
 ];
 
+assert.strictEqual(
+  dedentBlockString("\n    Before sprinting:\n      - restate scope\n\n    "),
+  "\nBefore sprinting:\n  - restate scope\n\n"
+);
+
+assert.deepStrictEqual(
+  parseJulietScript('juliet { engine = codex; }\ncreate Memo from juliet "Draft." using [Brief];\n').body.map((node) => [node.type, node.name]),
+  [["juliet", undefined], ["create", "Memo"]]
+);
+
 for (const test of tests) {
-  const diagnostics = lintJulietScript(test.source);
+  const diagnostics = lintJulietScript(test.source, test.options);
   try {
     test.validate(diagnostics);
   } catch (error) {